@@ -0,0 +1,91 @@
+use ndarray::Array2;
+use rayon::prelude::*;
+
+use crate::batch::CHUNK_LEN;
+use crate::{kernel, Result, SqrtError};
+
+/// Computes the elementwise square roots of a 2-D `ndarray` array.
+///
+/// The result has the same shape as `arr`. See [`sqrt_array_inplace`] to reuse the input's
+/// memory instead of allocating a second array.
+///
+/// # Arguments
+/// - `arr`: The input array (all elements must be non-negative).
+///
+/// # Returns
+/// - `Ok(Array2<f64>)` with the roots.
+/// - `Err(anyhow::Error)` if any element is negative or fails to converge.
+///
+/// ```
+/// use ndarray::array;
+///
+/// let roots = sqrtx::sqrt_array(&array![[1.0, 4.0], [9.0, 16.0]])?;
+/// assert_eq!(roots.shape(), [2, 2]);
+/// assert!((roots[[1, 0]] - 3.0).abs() < 1e-10);
+/// # Ok::<(), sqrtx::Error>(())
+/// ```
+pub fn sqrt_array(arr: &Array2<f64>) -> Result<Array2<f64>> {
+    let mut roots = arr.to_owned();
+    sqrt_array_inplace(&mut roots)?;
+    Ok(roots)
+}
+
+/// Replaces every element of a 2-D `ndarray` array with its square root.
+///
+/// Contiguous arrays, in either memory order, are split into chunks computed in parallel;
+/// other layouts (e.g. sliced views turned into owned arrays) are computed sequentially.
+///
+/// # Arguments
+/// - `arr`: The array to update (all elements must be non-negative).
+///
+/// # Returns
+/// - `Ok(())` if every element was replaced.
+/// - `Err(anyhow::Error)` if any element is negative or fails to converge. The array is then
+///   partially updated.
+pub fn sqrt_array_inplace(arr: &mut Array2<f64>) -> Result<()> {
+    let sqrt_in_place = |value: &mut f64| -> Result<(), SqrtError> {
+        *value = kernel::sqrt(*value)?;
+        Ok(())
+    };
+    match arr.as_slice_memory_order_mut() {
+        Some(values) => values
+            .par_chunks_mut(CHUNK_LEN)
+            .try_for_each(|chunk| chunk.iter_mut().try_for_each(sqrt_in_place))?,
+        None => arr.iter_mut().try_for_each(sqrt_in_place)?,
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::{s, Array};
+
+    use super::*;
+
+    #[test]
+    fn test_sqrt_array() -> Result<()> {
+        let arr = Array::from_shape_fn((3, CHUNK_LEN), |(i, j)| (i * CHUNK_LEN + j) as f64);
+        let roots = sqrt_array(&arr)?;
+        assert_eq!(roots.dim(), arr.dim());
+        for (root, value) in roots.iter().zip(&arr) {
+            assert!((root - value.sqrt()).abs() < 1e-10);
+        }
+
+        // Column-major and strided layouts give the same roots.
+        let mut transposed = arr.t().to_owned();
+        sqrt_array_inplace(&mut transposed)?;
+        assert_eq!(transposed.t(), roots);
+        let mut strided = arr.slice_move(s![.., ..;2]);
+        sqrt_array_inplace(&mut strided)?;
+        assert_eq!(strided, roots.slice(s![.., ..;2]));
+        Ok(())
+    }
+
+    #[test]
+    fn test_sqrt_array_negative() {
+        let mut arr = Array2::from_elem((2, 2), 4.0);
+        arr[[1, 1]] = -4.0;
+        assert!(sqrt_array(&arr).is_err());
+        assert!(sqrt_array_inplace(&mut arr).is_err());
+    }
+}
@@ -0,0 +1,999 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+#[cfg(feature = "async")]
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "async")]
+use futures::channel::oneshot;
+use rayon::prelude::*;
+#[cfg(feature = "async")]
+use tokio::task;
+
+use crate::quantize::{self, Quantization, Quantize};
+#[cfg(feature = "async")]
+use crate::OverloadPolicy;
+use crate::{kernel, mixed, Result};
+use crate::{ExecutionBackend, SqrtError, SqrtOptions, TelemetrySink};
+
+/// Number of elements handed to a worker at a time by the parallel backends.
+pub(crate) const CHUNK_LEN: usize = 4096;
+
+/// Summary of a batch computation returned alongside the results by the `*_with` batch APIs.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BatchReport {
+    /// Number of subnormal inputs or outputs that were flushed to zero.
+    pub flushed: usize,
+    /// Number of roots saturated into the [`SqrtOptions::clamp`] range or, in quantized batches,
+    /// the range of the integer type.
+    pub clamped: usize,
+    /// Number of input elements processed.
+    pub elements: usize,
+    /// Size of the processed input in bytes.
+    pub bytes: usize,
+    /// Wall-clock time spent on the batch.
+    pub elapsed: Duration,
+    /// Time spent computing, summed over every worker that took part in the batch.
+    pub busy: Duration,
+}
+
+impl BatchReport {
+    /// Input elements processed per second of wall-clock time.
+    pub fn elements_per_sec(&self) -> f64 {
+        per_sec(self.elements as f64, self.elapsed)
+    }
+
+    /// Input bytes processed per second of wall-clock time.
+    pub fn bytes_per_sec(&self) -> f64 {
+        per_sec(self.bytes as f64, self.elapsed)
+    }
+
+    /// Average number of workers busy during the batch (`busy / elapsed`).
+    pub fn effective_parallelism(&self) -> f64 {
+        if self.elapsed.is_zero() {
+            return 0.0;
+        }
+        self.busy.as_secs_f64() / self.elapsed.as_secs_f64()
+    }
+}
+
+fn per_sec(amount: f64, elapsed: Duration) -> f64 {
+    if elapsed.is_zero() {
+        return 0.0;
+    }
+    amount / elapsed.as_secs_f64()
+}
+
+/// Passes an event to the configured telemetry sink, if any.
+fn notify(options: &SqrtOptions, event: impl FnOnce(&dyn TelemetrySink)) {
+    if let Some(sink) = &options.telemetry {
+        event(sink.as_ref());
+    }
+}
+
+/// Flushes `value` to a zero of the same sign if it is subnormal and flushing is enabled.
+fn flush(value: f64, options: &SqrtOptions, report: &mut BatchReport) -> f64 {
+    if options.flush_denormals && value.is_subnormal() {
+        report.flushed += 1;
+        0.0_f64.copysign(value)
+    } else {
+        value
+    }
+}
+
+/// Runs the pipeline sequentially over one chunk, stopping at the first failing element.
+///
+/// The returned report counts the chunk's flushed values and busy time only.
+fn run_chunk<T, U, F>(
+    numbers: &[T],
+    options: &SqrtOptions,
+    element: &F,
+) -> Result<(Vec<U>, BatchReport), SqrtError>
+where
+    T: Copy,
+    F: Fn(T, &SqrtOptions, &mut BatchReport) -> Result<U, SqrtError>,
+{
+    let started = Instant::now();
+    let mut report = BatchReport::default();
+    let results = numbers
+        .iter()
+        .map(|&number| element(number, options, &mut report))
+        .collect::<Result<Vec<_>, SqrtError>>()?;
+
+    report.busy = started.elapsed();
+    notify(options, |sink| {
+        sink.chunk_completed(numbers.len(), report.busy)
+    });
+    Ok((results, report))
+}
+
+/// Decides whether chunks may still start, given the options' deadline and a cancellation flag.
+///
+/// A chunk is skipped once the flag is set, or if the deadline would pass while it runs,
+/// estimating its duration as the mean busy time of the chunks completed so far.
+struct Schedule<'a> {
+    deadline: Option<Instant>,
+    cancel: Option<&'a AtomicBool>,
+    busy_nanos: AtomicU64,
+    completed: AtomicUsize,
+}
+
+impl<'a> Schedule<'a> {
+    fn new(deadline: Option<Instant>, cancel: Option<&'a AtomicBool>) -> Self {
+        Self {
+            deadline,
+            cancel,
+            busy_nanos: AtomicU64::new(0),
+            completed: AtomicUsize::new(0),
+        }
+    }
+
+    /// Whether chunks need to be checked individually at all.
+    fn is_bounded(&self) -> bool {
+        self.deadline.is_some() || self.cancel.is_some()
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancel
+            .is_some_and(|cancel| cancel.load(Ordering::Relaxed))
+    }
+
+    fn admits(&self) -> bool {
+        if self.is_cancelled() {
+            return false;
+        }
+        let Some(deadline) = self.deadline else {
+            return true;
+        };
+        let completed = self.completed.load(Ordering::Relaxed) as u64;
+        let estimate = match completed {
+            0 => Duration::ZERO,
+            _ => Duration::from_nanos(self.busy_nanos.load(Ordering::Relaxed) / completed),
+        };
+        Instant::now() + estimate <= deadline
+    }
+
+    fn record(&self, busy: Duration) {
+        let nanos = u64::try_from(busy.as_nanos()).unwrap_or(u64::MAX);
+        self.busy_nanos.fetch_add(nanos, Ordering::Relaxed);
+        self.completed.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Runs one chunk, starting at input `start`, if the schedule still admits it, and reduces its
+/// results; returns `None` if it was skipped.
+fn run_scheduled<T, U, C, F, R>(
+    chunk: &[T],
+    start: usize,
+    options: &SqrtOptions,
+    element: &F,
+    reduce: &R,
+    schedule: &Schedule,
+) -> Result<Option<(C, BatchReport)>, SqrtError>
+where
+    T: Copy,
+    F: Fn(T, &SqrtOptions, &mut BatchReport) -> Result<U, SqrtError>,
+    R: Fn(usize, Vec<U>) -> C,
+{
+    if !schedule.admits() {
+        return Ok(None);
+    }
+    let (results, report) = run_chunk(chunk, options, element)?;
+    schedule.record(report.busy);
+    Ok(Some((reduce(start, results), report)))
+}
+
+/// Adds the flushed and clamped counts and busy time of a chunk to the batch report.
+fn add_chunk_report(report: &mut BatchReport, chunk: &BatchReport) {
+    report.flushed += chunk.flushed;
+    report.clamped += chunk.clamped;
+    report.busy += chunk.busy;
+}
+
+/// Concatenates chunk results in input order and merges their reports.
+///
+/// If any chunk was skipped the batch fails with [`SqrtError::Cancelled`], or with
+/// [`SqrtError::DeadlineExceeded`] carrying the computed roots with `NaN` in place of the
+/// skipped ones.
+fn merge_chunks<U: Into<f64>>(
+    chunks: Vec<Option<(Vec<U>, BatchReport)>>,
+    elements: usize,
+    chunk_len: usize,
+    schedule: &Schedule,
+) -> Result<(Vec<U>, BatchReport), SqrtError> {
+    if chunks.iter().any(Option::is_none) {
+        if schedule.is_cancelled() {
+            let completed = chunks.iter().flatten().map(|(results, _)| results.len());
+            return Err(SqrtError::Cancelled {
+                completed: completed.sum(),
+                elements,
+            });
+        }
+        let mut partial = Vec::with_capacity(elements);
+        for chunk in chunks {
+            match chunk {
+                Some((results, _)) => partial.extend(results.into_iter().map(Into::into)),
+                None => {
+                    let missing = chunk_len.min(elements - partial.len());
+                    partial.resize(partial.len() + missing, f64::NAN);
+                }
+            }
+        }
+        let completed = partial.iter().filter(|root| !root.is_nan()).count();
+        return Err(SqrtError::DeadlineExceeded {
+            completed,
+            elements,
+            partial,
+        });
+    }
+
+    let mut results = Vec::with_capacity(elements);
+    let mut report = BatchReport::default();
+    for (chunk, chunk_report) in chunks.into_iter().flatten() {
+        results.extend(chunk);
+        add_chunk_report(&mut report, &chunk_report);
+    }
+
+    Ok((results, report))
+}
+
+/// Collects folded chunks in input order and merges their reports.
+///
+/// If any chunk was skipped the batch fails like [`merge_chunks`], except that the `partial`
+/// roots of [`SqrtError::DeadlineExceeded`] are empty, as they were never materialized.
+fn merge_folded<C>(
+    chunks: Vec<Option<(C, BatchReport)>>,
+    elements: usize,
+    schedule: &Schedule,
+) -> Result<(Vec<C>, BatchReport), SqrtError> {
+    if chunks.iter().any(Option::is_none) {
+        let completed = (0..chunks.len())
+            .filter(|&index| chunks[index].is_some())
+            .map(|index| CHUNK_LEN.min(elements - index * CHUNK_LEN))
+            .sum();
+        if schedule.is_cancelled() {
+            return Err(SqrtError::Cancelled {
+                completed,
+                elements,
+            });
+        }
+        return Err(SqrtError::DeadlineExceeded {
+            completed,
+            elements,
+            partial: Vec::new(),
+        });
+    }
+
+    let mut folded = Vec::with_capacity(chunks.len());
+    let mut report = BatchReport::default();
+    for (chunk, chunk_report) in chunks.into_iter().flatten() {
+        folded.push(chunk);
+        add_chunk_report(&mut report, &chunk_report);
+    }
+    Ok((folded, report))
+}
+
+/// Runs the pipeline over rayon chunks on the current pool, reducing each chunk as it completes.
+fn run_parallel<T, U, C, F, R>(
+    numbers: &[T],
+    options: &SqrtOptions,
+    element: &F,
+    reduce: &R,
+    schedule: &Schedule,
+) -> Result<Vec<Option<(C, BatchReport)>>, SqrtError>
+where
+    T: Copy + Sync,
+    C: Send,
+    F: Fn(T, &SqrtOptions, &mut BatchReport) -> Result<U, SqrtError> + Sync,
+    R: Fn(usize, Vec<U>) -> C + Sync,
+{
+    numbers
+        .par_chunks(CHUNK_LEN)
+        .enumerate()
+        .map(|(index, chunk)| {
+            run_scheduled(chunk, index * CHUNK_LEN, options, element, reduce, schedule)
+        })
+        .collect()
+}
+
+/// Runs the pipeline on the calling thread, in chunks only if there is a deadline or
+/// cancellation flag to check.
+fn run_sequential<T, U, C, F, R>(
+    numbers: &[T],
+    options: &SqrtOptions,
+    element: &F,
+    reduce: &R,
+    schedule: &Schedule,
+) -> Result<Vec<Option<(C, BatchReport)>>, SqrtError>
+where
+    T: Copy,
+    F: Fn(T, &SqrtOptions, &mut BatchReport) -> Result<U, SqrtError>,
+    R: Fn(usize, Vec<U>) -> C,
+{
+    if !schedule.is_bounded() {
+        let (results, report) = run_chunk(numbers, options, element)?;
+        return Ok(vec![Some((reduce(0, results), report))]);
+    }
+
+    numbers
+        .chunks(CHUNK_LEN)
+        .enumerate()
+        .map(|(index, chunk)| {
+            run_scheduled(chunk, index * CHUNK_LEN, options, element, reduce, schedule)
+        })
+        .collect()
+}
+
+/// Runs `element` over every input on the selected backend, stopping between chunks once
+/// `cancel` is set.
+fn run_batch_by<T, U, F>(
+    numbers: &[T],
+    options: &SqrtOptions,
+    cancel: Option<&AtomicBool>,
+    element: F,
+) -> Result<(Vec<U>, BatchReport), SqrtError>
+where
+    T: Copy + Sync,
+    U: Send + Into<f64>,
+    F: Fn(T, &SqrtOptions, &mut BatchReport) -> Result<U, SqrtError> + Sync,
+{
+    let keep = |_, results| results;
+    run_batch_reduced(
+        numbers,
+        options,
+        cancel,
+        element,
+        keep,
+        |chunks, schedule| merge_chunks(chunks, numbers.len(), CHUNK_LEN, schedule),
+    )
+}
+
+/// Like [`run_batch_by`], but passes each chunk's results to `reduce`, with the index of its
+/// first input, as soon as the chunk completes, and the reduced chunks to `merge`.
+fn run_batch_reduced<T, U, C, O, F, R, M>(
+    numbers: &[T],
+    options: &SqrtOptions,
+    cancel: Option<&AtomicBool>,
+    element: F,
+    reduce: R,
+    merge: M,
+) -> Result<(O, BatchReport), SqrtError>
+where
+    T: Copy + Sync,
+    C: Send,
+    F: Fn(T, &SqrtOptions, &mut BatchReport) -> Result<U, SqrtError> + Sync,
+    R: Fn(usize, Vec<U>) -> C + Sync,
+    M: FnOnce(Vec<Option<(C, BatchReport)>>, &Schedule) -> Result<(O, BatchReport), SqrtError>,
+{
+    let started = Instant::now();
+    notify(options, |sink| sink.job_started(numbers.len()));
+    let schedule = Schedule::new(options.deadline, cancel);
+    let chunks = match &options.backend {
+        ExecutionBackend::Sequential | ExecutionBackend::TokioBlocking => {
+            run_sequential(numbers, options, &element, &reduce, &schedule)
+        }
+        ExecutionBackend::Rayon => run_parallel(numbers, options, &element, &reduce, &schedule),
+        ExecutionBackend::CustomPool(pool) => {
+            pool.install(|| run_parallel(numbers, options, &element, &reduce, &schedule))
+        }
+    };
+    let (results, mut report) = chunks
+        .and_then(|chunks| merge(chunks, &schedule))
+        .inspect_err(|error| notify(options, |sink| sink.error(error)))?;
+
+    report.elements = numbers.len();
+    report.bytes = size_of_val(numbers);
+    report.elapsed = started.elapsed();
+    notify(options, |sink| sink.job_finished(&report));
+    Ok((results, report))
+}
+
+/// Runs the configured pipeline over `numbers` on the selected backend.
+pub(crate) fn run_batch(
+    numbers: Vec<f64>,
+    options: &SqrtOptions,
+) -> Result<(Vec<f64>, BatchReport), SqrtError> {
+    run_batch_cancellable(&numbers, options, None)
+}
+
+/// Like [`run_batch`], but fails with [`SqrtError::Cancelled`] once `cancel` is set.
+pub(crate) fn run_batch_cancellable(
+    numbers: &[f64],
+    options: &SqrtOptions,
+    cancel: Option<&AtomicBool>,
+) -> Result<(Vec<f64>, BatchReport), SqrtError> {
+    run_batch_by(numbers, options, cancel, sqrt_element)
+}
+
+/// Runs the configured pipeline over `numbers`, folding each chunk's roots with `fold`, along
+/// with the index of the chunk's first input, as soon as the chunk completes, so that the full
+/// output is never materialized. Returns the folded chunks in input order.
+pub(crate) fn run_batch_folded<C, R>(
+    numbers: &[f64],
+    options: &SqrtOptions,
+    fold: R,
+) -> Result<(Vec<C>, BatchReport), SqrtError>
+where
+    C: Send,
+    R: Fn(usize, Vec<f64>) -> C + Sync,
+{
+    run_batch_reduced(
+        numbers,
+        options,
+        None,
+        sqrt_element,
+        fold,
+        |chunks, schedule| merge_folded(chunks, numbers.len(), schedule),
+    )
+}
+
+/// Computes one `f64` root with the per-element options: flushing, convergence and clamping.
+pub(crate) fn sqrt_element(
+    number: f64,
+    options: &SqrtOptions,
+    report: &mut BatchReport,
+) -> Result<f64, SqrtError> {
+    let number = flush(number, options, report);
+    let root = kernel::sqrt_with(number, options)?;
+    Ok(clamp(flush(root, options, report), options, report))
+}
+
+/// Runs the configured pipeline over `numbers` and quantizes every root in the same pass.
+pub(crate) fn run_batch_quantized<Q: Quantize>(
+    numbers: &[f64],
+    options: &SqrtOptions,
+    quantization: &Quantization,
+) -> Result<(Vec<Q>, BatchReport), SqrtError> {
+    // A zero or `NaN` scale would quantize every root to zero rather than to `zero_point`.
+    if !(quantization.scale > 0.0 && quantization.scale.is_finite()) {
+        return Err(SqrtError::InvalidScale(quantization.scale));
+    }
+    run_batch_by(numbers, options, None, |number, options, report| {
+        let root = sqrt_element(number, options, report)?;
+        Ok(quantize::quantize(root, quantization, report))
+    })
+}
+
+/// Saturates `root` into the configured clamp range, if any.
+pub(crate) fn clamp(root: f64, options: &SqrtOptions, report: &mut BatchReport) -> f64 {
+    let Some((low, high)) = options.clamp else {
+        return root;
+    };
+    // Comparisons rather than `f64::clamp`, which panics on an inverted range.
+    let clamped = if root < low {
+        low
+    } else if root > high {
+        high
+    } else {
+        return root;
+    };
+    report.clamped += 1;
+    clamped
+}
+
+/// Flushes `value` to a zero of the same sign if it is an f32 subnormal and flushing is enabled.
+fn flush_f32(value: f32, options: &SqrtOptions, report: &mut BatchReport) -> f32 {
+    if options.flush_denormals && value.is_subnormal() {
+        report.flushed += 1;
+        0.0_f32.copysign(value)
+    } else {
+        value
+    }
+}
+
+/// Runs another scalar kernel, such as the inverse square root or the cube root, over `numbers`,
+/// flushing subnormal inputs as configured.
+pub(crate) fn run_batch_kernel(
+    numbers: &[f64],
+    options: &SqrtOptions,
+    kernel: fn(f64) -> Result<f64, SqrtError>,
+) -> Result<(Vec<f64>, BatchReport), SqrtError> {
+    run_batch_by(numbers, options, None, |number, options, report| {
+        kernel(flush(number, options, report))
+    })
+}
+
+/// Runs the pipeline over f32 inputs, computing and returning the roots in f64.
+pub(crate) fn run_batch_f32(
+    numbers: &[f32],
+    options: &SqrtOptions,
+) -> Result<(Vec<f64>, BatchReport), SqrtError> {
+    run_batch_by(numbers, options, None, |number, options, report| {
+        let root = mixed::widened_sqrt(flush_f32(number, options, report))?;
+        Ok(clamp(root, options, report))
+    })
+}
+
+/// Runs the pipeline over f32 inputs, returning the correctly rounded f32 roots.
+pub(crate) fn run_batch_f32_rounded(
+    numbers: &[f32],
+    options: &SqrtOptions,
+) -> Result<(Vec<f32>, BatchReport), SqrtError> {
+    run_batch_by(numbers, options, None, |number, options, report| {
+        mixed::rounded_sqrt_f32(flush_f32(number, options, report))
+    })
+}
+
+/// Outcome of scanning a batch without computing it, returned by
+/// [`validate_batch`](crate::validate_batch).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    /// Number of input elements scanned.
+    pub elements: usize,
+    /// Number of negative inputs (including `-∞`), which fail with
+    /// [`SqrtError::NegativeNumber`].
+    pub negatives: usize,
+    /// Number of `NaN` inputs, which fail to converge.
+    pub nans: usize,
+    /// Number of `+∞` inputs, which fail to converge.
+    pub infinities: usize,
+    /// Number of subnormal inputs that would be flushed to zero, losing their value.
+    pub flushed: usize,
+}
+
+impl ValidationReport {
+    /// Number of inputs that would make the batch fail.
+    pub fn failures(&self) -> usize {
+        self.negatives + self.nans + self.infinities
+    }
+
+    /// Whether the batch would run to completion.
+    pub fn is_valid(&self) -> bool {
+        self.failures() == 0
+    }
+
+    fn count(mut self, number: f64, options: &SqrtOptions) -> Self {
+        self.elements += 1;
+        if number.is_nan() {
+            self.nans += 1;
+        } else if number < 0.0 {
+            self.negatives += 1;
+        } else if number.is_infinite() {
+            self.infinities += 1;
+        } else if options.flush_denormals && number.is_subnormal() {
+            self.flushed += 1;
+        }
+        self
+    }
+
+    fn merge(self, other: Self) -> Self {
+        Self {
+            elements: self.elements + other.elements,
+            negatives: self.negatives + other.negatives,
+            nans: self.nans + other.nans,
+            infinities: self.infinities + other.infinities,
+            flushed: self.flushed + other.flushed,
+        }
+    }
+}
+
+/// Scans `numbers` on the selected backend, counting the inputs that would fail or lose
+/// precision, without computing any square roots.
+pub(crate) fn validate(numbers: &[f64], options: &SqrtOptions) -> ValidationReport {
+    let scan = || {
+        numbers
+            .par_iter()
+            .fold(ValidationReport::default, |report, &number| {
+                report.count(number, options)
+            })
+            .reduce(ValidationReport::default, ValidationReport::merge)
+    };
+    match &options.backend {
+        ExecutionBackend::Sequential | ExecutionBackend::TokioBlocking => numbers
+            .iter()
+            .fold(ValidationReport::default(), |report, &number| {
+                report.count(number, options)
+            }),
+        ExecutionBackend::Rayon => scan(),
+        ExecutionBackend::CustomPool(pool) => pool.install(scan),
+    }
+}
+
+/// Runs the single-precision fast path over `numbers`, without widening to f64.
+pub(crate) fn run_batch_f32_fast(
+    numbers: &[f32],
+    options: &SqrtOptions,
+) -> Result<(Vec<f32>, BatchReport), SqrtError> {
+    run_batch_by(numbers, options, None, |number, options, report| {
+        let number = flush_f32(number, options, report);
+        let root = kernel::newton_sqrt_f32(number)?;
+        Ok(flush_f32(root, options, report))
+    })
+}
+
+#[cfg(feature = "async")]
+/// Async counterpart of [`run_batch`] that never blocks the calling task on computation, except
+/// for [`ExecutionBackend::Sequential`] which deliberately runs inline.
+pub(crate) async fn run_batch_async(
+    numbers: Vec<f64>,
+    options: SqrtOptions,
+) -> Result<(Vec<f64>, BatchReport)> {
+    match options.backend.clone() {
+        ExecutionBackend::Sequential => Ok(run_batch(numbers, &options)?),
+        ExecutionBackend::TokioBlocking => match options.overload {
+            Some(policy) => run_blocking_or_shed(numbers, options, policy).await,
+            None => task::spawn_blocking(move || Ok(run_batch(numbers, &options)?)).await?,
+        },
+        ExecutionBackend::Rayon => {
+            let (sender, receiver) = oneshot::channel();
+            rayon::spawn(move || {
+                let _ = sender.send(run_batch(numbers, &options));
+            });
+            Ok(receiver.await??)
+        }
+        ExecutionBackend::CustomPool(pool) => {
+            let (sender, receiver) = oneshot::channel();
+            pool.spawn(move || {
+                let _ = sender.send(run_batch(numbers, &options));
+            });
+            Ok(receiver.await??)
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+/// Offloads the batch to the blocking pool, taking it back if no thread picks it up in time.
+///
+/// The input sits in a shared slot; whichever side empties the slot first (the blocking thread
+/// when it starts, or this task once the delay expires) computes the batch, so it never runs
+/// twice.
+async fn run_blocking_or_shed(
+    numbers: Vec<f64>,
+    options: SqrtOptions,
+    policy: OverloadPolicy,
+) -> Result<(Vec<f64>, BatchReport)> {
+    let elements = numbers.len();
+    let slot = Arc::new(Mutex::new(Some(numbers)));
+    let claim =
+        |slot: &Mutex<Option<Vec<f64>>>| slot.lock().unwrap_or_else(PoisonError::into_inner).take();
+
+    let mut handle = task::spawn_blocking({
+        let slot = Arc::clone(&slot);
+        let options = options.clone();
+        move || claim(&slot).map(|numbers| run_batch(numbers, &options))
+    });
+
+    let joined = match tokio::time::timeout(policy.max_queue_delay, &mut handle).await {
+        Ok(joined) => joined,
+        Err(_) => match claim(&slot) {
+            Some(numbers) => {
+                handle.abort();
+                if elements > policy.inline_limit {
+                    let error = SqrtError::Overloaded {
+                        elements,
+                        waited: policy.max_queue_delay,
+                    };
+                    notify(&options, |sink| sink.error(&error));
+                    return Err(error)?;
+                }
+                return Ok(run_batch(numbers, &options)?);
+            }
+            // A blocking thread already picked the batch up; wait for it to finish.
+            None => handle.await,
+        },
+    };
+
+    match joined? {
+        Some(result) => Ok(result?),
+        None => unreachable!("the batch is only claimed by the blocking task or after the timeout"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    #[test]
+    fn test_throughput() {
+        let report = BatchReport {
+            elements: 1_000,
+            bytes: 8_000,
+            elapsed: Duration::from_millis(500),
+            busy: Duration::from_secs(2),
+            ..BatchReport::default()
+        };
+        assert_eq!(report.elements_per_sec(), 2_000.0);
+        assert_eq!(report.bytes_per_sec(), 16_000.0);
+        assert_eq!(report.effective_parallelism(), 4.0);
+    }
+
+    #[test]
+    fn test_throughput_empty_batch() {
+        let report = BatchReport::default();
+        assert_eq!(report.elements_per_sec(), 0.0);
+        assert_eq!(report.effective_parallelism(), 0.0);
+    }
+
+    #[test]
+    fn test_backends_agree() -> anyhow::Result<()> {
+        let numbers: Vec<f64> = (0..3 * CHUNK_LEN + 7).map(|i| i as f64).collect();
+        let pool = Arc::new(rayon::ThreadPoolBuilder::new().num_threads(2).build()?);
+        let (expected, _) = run_batch(
+            numbers.clone(),
+            &SqrtOptions::new().backend(ExecutionBackend::Sequential),
+        )?;
+
+        for backend in [ExecutionBackend::Rayon, ExecutionBackend::CustomPool(pool)] {
+            let (results, report) =
+                run_batch(numbers.clone(), &SqrtOptions::new().backend(backend))?;
+            assert_eq!(results, expected);
+            assert_eq!(report.elements, numbers.len());
+            assert!(report.busy > Duration::ZERO);
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_async_backends_agree() -> anyhow::Result<()> {
+        let rt = tokio::runtime::Runtime::new()?;
+        let numbers: Vec<f64> = (0..CHUNK_LEN + 1).map(|i| i as f64).collect();
+        let (expected, _) = run_batch(numbers.clone(), &SqrtOptions::new())?;
+
+        for backend in [
+            ExecutionBackend::Sequential,
+            ExecutionBackend::TokioBlocking,
+            ExecutionBackend::Rayon,
+        ] {
+            let options = SqrtOptions::new().backend(backend);
+            let (results, _) = rt.block_on(run_batch_async(numbers.clone(), options))?;
+            assert_eq!(results, expected);
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_overloaded_blocking_pool() -> anyhow::Result<()> {
+        let rt = tokio::runtime::Builder::new_multi_thread()
+            .max_blocking_threads(1)
+            .enable_time()
+            .build()?;
+        let policy = OverloadPolicy {
+            max_queue_delay: Duration::from_millis(10),
+            inline_limit: 2,
+        };
+        let options = SqrtOptions::new().overload(policy);
+
+        rt.block_on(async {
+            let busy = task::spawn_blocking(|| std::thread::sleep(Duration::from_millis(300)));
+
+            let (results, _) = run_batch_async(vec![4.0, 9.0], options.clone()).await?;
+            assert_eq!(results, vec![2.0, 3.0]);
+
+            let error = run_batch_async(vec![4.0, 9.0, 16.0], options)
+                .await
+                .unwrap_err();
+            assert!(matches!(
+                crate::error::as_sqrt_error(&error),
+                Some(SqrtError::Overloaded { elements: 3, .. })
+            ));
+
+            busy.await?;
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_f32_backends_agree() -> anyhow::Result<()> {
+        let numbers: Vec<f32> = (0..2 * CHUNK_LEN + 3).map(|i| i as f32 * 0.25).collect();
+        let sequential = SqrtOptions::new().backend(ExecutionBackend::Sequential);
+        let parallel = SqrtOptions::new().backend(ExecutionBackend::Rayon);
+
+        assert_eq!(
+            run_batch_f32(&numbers, &sequential)?.0,
+            run_batch_f32(&numbers, &parallel)?.0
+        );
+        assert_eq!(
+            run_batch_f32_rounded(&numbers, &sequential)?.0,
+            run_batch_f32_rounded(&numbers, &parallel)?.0
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_clamp_outputs() -> anyhow::Result<()> {
+        let numbers: Vec<f64> = (0..2 * CHUNK_LEN + 3).map(|i| i as f64).collect();
+        for backend in [ExecutionBackend::Sequential, ExecutionBackend::Rayon] {
+            let options = SqrtOptions::new().backend(backend).clamp(1.5, 10.0);
+            let (results, report) = run_batch(numbers.clone(), &options)?;
+            assert_eq!(results[..3], [1.5, 1.5, 1.5]);
+            assert!((results[4] - 2.0).abs() < 1e-10);
+            assert_eq!(results[numbers.len() - 1], 10.0);
+            // √0, √1 and √2 below the range; √101 and above over it.
+            assert_eq!(report.clamped, 3 + numbers.len() - 101);
+        }
+
+        let options = SqrtOptions::new().clamp(0.0, 1.0);
+        let (results, report) = run_batch_f32(&[0.25, 9.0], &options)?;
+        assert_eq!(results, [0.5, 1.0]);
+        assert_eq!(report.clamped, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_quantized_backends_agree() -> anyhow::Result<()> {
+        let numbers: Vec<f64> = (0..2 * CHUNK_LEN + 3).map(|i| i as f64).collect();
+        let quantization = Quantization::new(0.25, 0);
+        let mut outputs = Vec::new();
+        for backend in [ExecutionBackend::Sequential, ExecutionBackend::Rayon] {
+            let options = SqrtOptions::new().backend(backend);
+            let (values, report) = run_batch_quantized::<u8>(&numbers, &options, &quantization)?;
+            assert_eq!(values[..3], [0, 4, 6]);
+            // Roots above 63.875 saturate at 255.
+            assert_eq!(
+                report.clamped,
+                numbers.iter().filter(|&&x| x.sqrt() > 63.875).count()
+            );
+            outputs.push(values);
+        }
+        assert_eq!(outputs[0], outputs[1]);
+        assert!(run_batch_quantized::<i16>(&[-1.0], &SqrtOptions::new(), &quantization).is_err());
+
+        for scale in [0.0, -0.25, f64::NAN, f64::INFINITY] {
+            let quantization = Quantization::new(scale, 3);
+            assert!(matches!(
+                run_batch_quantized::<u8>(&[0.0], &SqrtOptions::new(), &quantization),
+                Err(SqrtError::InvalidScale(_))
+            ));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_f32_flush_denormals() -> anyhow::Result<()> {
+        let options = SqrtOptions::new().flush_denormals(true);
+        let (results, report) = run_batch_f32(&[f32::from_bits(1), 4.0], &options)?;
+        assert_eq!(results, vec![0.0, 2.0]);
+        assert_eq!(report.flushed, 1);
+        assert_eq!(report.bytes, 2 * size_of::<f32>());
+        Ok(())
+    }
+
+    #[derive(Debug, Default)]
+    struct CountingTelemetry {
+        events: Mutex<Vec<&'static str>>,
+        chunk_elements: AtomicUsize,
+    }
+
+    impl TelemetrySink for CountingTelemetry {
+        fn job_started(&self, _: usize) {
+            self.events.lock().unwrap().push("started");
+        }
+
+        fn chunk_completed(&self, elements: usize, _: Duration) {
+            self.chunk_elements.fetch_add(elements, Ordering::Relaxed);
+        }
+
+        fn job_finished(&self, _: &BatchReport) {
+            self.events.lock().unwrap().push("finished");
+        }
+
+        fn error(&self, _: &SqrtError) {
+            self.events.lock().unwrap().push("error");
+        }
+    }
+
+    #[test]
+    fn test_telemetry_events() {
+        let telemetry = Arc::new(CountingTelemetry::default());
+        let options = SqrtOptions::new()
+            .backend(ExecutionBackend::Rayon)
+            .telemetry(telemetry.clone());
+
+        let numbers = vec![4.0; 2 * CHUNK_LEN + 1];
+        assert!(run_batch(numbers.clone(), &options).is_ok());
+        assert!(run_batch(vec![-1.0], &options).is_err());
+
+        assert_eq!(
+            *telemetry.events.lock().unwrap(),
+            ["started", "finished", "started", "error"]
+        );
+        assert_eq!(
+            telemetry.chunk_elements.load(Ordering::Relaxed),
+            numbers.len()
+        );
+    }
+
+    #[test]
+    fn test_deadline_exceeded() {
+        let numbers = vec![4.0; 3 * CHUNK_LEN];
+        for backend in [ExecutionBackend::Sequential, ExecutionBackend::Rayon] {
+            let options = SqrtOptions::new().backend(backend).deadline(Instant::now());
+            match run_batch(numbers.clone(), &options) {
+                Err(SqrtError::DeadlineExceeded {
+                    completed,
+                    elements,
+                    partial,
+                }) => {
+                    assert_eq!(elements, numbers.len());
+                    assert_eq!(partial.len(), numbers.len());
+                    assert_eq!(
+                        partial.iter().filter(|root| **root == 2.0).count(),
+                        completed
+                    );
+                }
+                other => panic!("expected a deadline error, got {other:?}"),
+            }
+        }
+
+        let options = SqrtOptions::new().deadline(Instant::now() + Duration::from_secs(60));
+        assert!(run_batch(numbers, &options).is_ok());
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_async_deadline_exceeded() -> anyhow::Result<()> {
+        let rt = tokio::runtime::Runtime::new()?;
+        let options = SqrtOptions::new().deadline(Instant::now());
+        let error = rt
+            .block_on(run_batch_async(vec![4.0; 2 * CHUNK_LEN], options))
+            .unwrap_err();
+        assert!(matches!(
+            crate::error::as_sqrt_error(&error),
+            Some(SqrtError::DeadlineExceeded { .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_cancelled() {
+        let numbers = vec![4.0; 3 * CHUNK_LEN];
+        let cancel = AtomicBool::new(true);
+        for backend in [ExecutionBackend::Sequential, ExecutionBackend::Rayon] {
+            let options = SqrtOptions::new().backend(backend);
+            assert!(matches!(
+                run_batch_cancellable(&numbers, &options, Some(&cancel)),
+                Err(SqrtError::Cancelled {
+                    completed: 0,
+                    elements: 12_288,
+                })
+            ));
+        }
+
+        cancel.store(false, Ordering::Relaxed);
+        let (results, _) = run_batch_cancellable(&numbers, &SqrtOptions::new(), Some(&cancel))
+            .expect("an unset flag does not cancel");
+        assert_eq!(results, vec![2.0; numbers.len()]);
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut numbers = vec![1.0; 2 * CHUNK_LEN];
+        numbers[1] = -2.0;
+        numbers[2] = f64::NEG_INFINITY;
+        numbers[3] = f64::NAN;
+        numbers[CHUNK_LEN] = f64::INFINITY;
+        numbers[CHUNK_LEN + 1] = f64::MIN_POSITIVE / 2.0;
+
+        let expected = ValidationReport {
+            elements: numbers.len(),
+            negatives: 2,
+            nans: 1,
+            infinities: 1,
+            flushed: 1,
+        };
+        for backend in [ExecutionBackend::Sequential, ExecutionBackend::Rayon] {
+            let options = SqrtOptions::new().flush_denormals(true).backend(backend);
+            let report = validate(&numbers, &options);
+            assert_eq!(report, expected);
+            assert_eq!(report.failures(), 4);
+            assert!(!report.is_valid());
+        }
+        assert_eq!(validate(&numbers, &SqrtOptions::new()).flushed, 0);
+        assert!(validate(&[0.0, 4.0], &SqrtOptions::new()).is_valid());
+    }
+
+    #[test]
+    fn test_parallel_batch_with_negative() {
+        let mut numbers = vec![1.0; 2 * CHUNK_LEN];
+        numbers[CHUNK_LEN + 3] = -2.0;
+        let options = SqrtOptions::new().backend(ExecutionBackend::Rayon);
+        assert!(matches!(
+            run_batch(numbers, &options),
+            Err(SqrtError::NegativeNumber(_))
+        ));
+    }
+}
@@ -0,0 +1,239 @@
+//! Slice kernels for `half::bf16`, the storage format of most embedding and feature tables.
+//!
+//! Unlike the generic [`SqrtFloat`] implementation, which takes each root through the crate
+//! kernel, these widen eight values at a time, work in `f32` (in `wide::f32x8` registers with
+//! the `simd` feature) and round to `bf16` once at the end, using the hardware square root.
+//! `bf16` has the exponent range of `f32`, so widening is exact.
+//!
+//! The scalar and SIMD paths accumulate in the same eight-lane order, so results do not depend
+//! on the `simd` feature.
+
+use half::bf16;
+use rayon::prelude::*;
+
+use crate::preprocess::check_shape;
+use crate::{hw, kernel, Result, SqrtError, SqrtFloat};
+#[cfg(feature = "simd")]
+use wide::f32x8;
+
+const LANES: usize = 8;
+
+/// Number of elements per partition when taking the roots of a single slice in parallel.
+const CHUNK_LEN: usize = 8192;
+
+fn widen(chunk: &[bf16]) -> [f32; LANES] {
+    let mut lanes = [0.0; LANES];
+    for (lane, value) in lanes.iter_mut().zip(chunk) {
+        *lane = value.to_f32();
+    }
+    lanes
+}
+
+/// Returns `Σ xᵢ²` accumulated in `f32`, with element `i` going to lane `i % 8`.
+#[cfg(feature = "simd")]
+fn sum_sq_f32(row: &[bf16]) -> f32 {
+    let mut sum = f32x8::ZERO;
+    for chunk in row.chunks(LANES) {
+        let lanes = f32x8::new(widen(chunk));
+        sum += lanes * lanes;
+    }
+    sum.to_array().iter().sum()
+}
+
+/// Returns `Σ xᵢ²` accumulated in `f32`, with element `i` going to lane `i % 8`.
+#[cfg(not(feature = "simd"))]
+fn sum_sq_f32(row: &[bf16]) -> f32 {
+    let mut sum = [0.0f32; LANES];
+    for chunk in row.chunks(LANES) {
+        for (sum, lane) in sum.iter_mut().zip(widen(chunk)) {
+            *sum += lane * lane;
+        }
+    }
+    sum.iter().sum()
+}
+
+/// Writes the roots of `chunk` to `roots`, which must be as long. Every value must already be
+/// known to be non-negative and finite.
+#[cfg(feature = "simd")]
+fn sqrt_chunk(chunk: &[bf16], roots: &mut [bf16]) {
+    for (chunk, roots) in chunk.chunks(LANES).zip(roots.chunks_mut(LANES)) {
+        let lanes = f32x8::new(widen(chunk)).sqrt().to_array();
+        for (root, lane) in roots.iter_mut().zip(lanes) {
+            *root = bf16::from_f32(lane);
+        }
+    }
+}
+
+/// Writes the roots of `chunk` to `roots`, which must be as long. Every value must already be
+/// known to be non-negative and finite.
+#[cfg(not(feature = "simd"))]
+fn sqrt_chunk(chunk: &[bf16], roots: &mut [bf16]) {
+    for (root, value) in roots.iter_mut().zip(chunk) {
+        *root = bf16::from_f64(hw::sqrt(value.to_f64()));
+    }
+}
+
+/// Returns `√(Σ xᵢ²)` for one row, rounded to `bf16` once.
+fn row_norm(row: &[bf16]) -> Result<bf16, SqrtError> {
+    let sum = sum_sq_f32(row);
+    if sum.is_nan() {
+        return kernel::sqrt(f64::NAN).map(bf16::from_f64);
+    }
+    // Squares of large entries can overflow `f32` and squares of tiny ones underflow it, but
+    // never in `f64`, so such rare rows are summed again there.
+    let sum = if sum.is_infinite() || (sum < f32::MIN_POSITIVE && sum_sq_f64(row) > 0.0) {
+        sum_sq_f64(row)
+    } else {
+        f64::from(sum)
+    };
+    Ok(bf16::from_f64(hw::sqrt(sum)))
+}
+
+fn sum_sq_f64(row: &[bf16]) -> f64 {
+    row.iter()
+        .map(|value| value.to_f64() * value.to_f64())
+        .sum()
+}
+
+/// Computes the square roots of a slice of `bf16` values in parallel.
+///
+/// Every root is the correctly rounded `bf16` root, exactly as [`square_root`](crate::square_root)
+/// gives for each value, but computed eight lanes at a time in `f32` and rounded once, for the
+/// throughput of feature pipelines.
+///
+/// # Arguments
+/// - `numbers`: The input numbers (each must be non-negative and finite).
+///
+/// # Returns
+/// - `Ok(Vec<bf16>)` with the roots, in input order.
+/// - `Err(anyhow::Error)` for the first value, in index order, that is negative, infinite or
+///   `NaN`, with the error [`square_root`](crate::square_root) reports for it.
+///
+/// ```
+/// use half::bf16;
+///
+/// let numbers = [4.0, 2.0, 0.25].map(bf16::from_f32);
+/// let roots = sqrtx::square_roots_bf16(&numbers)?;
+/// assert_eq!(roots, [2.0, 1.4140625, 0.5].map(bf16::from_f32));
+/// # Ok::<(), sqrtx::Error>(())
+/// ```
+pub fn square_roots_bf16(numbers: &[bf16]) -> Result<Vec<bf16>> {
+    if let Some(&number) = numbers
+        .iter()
+        .find(|number| !(number.to_f32() >= 0.0 && number.is_finite()))
+    {
+        number.kernel_sqrt()?;
+    }
+    let mut roots = vec![bf16::ZERO; numbers.len()];
+    numbers
+        .par_chunks(CHUNK_LEN)
+        .zip(roots.par_chunks_mut(CHUNK_LEN))
+        .for_each(|(chunk, roots)| sqrt_chunk(chunk, roots));
+    Ok(roots)
+}
+
+/// Computes the Euclidean norm of every row of a row-major `bf16` matrix, such as a table of
+/// embeddings, in parallel.
+///
+/// Each row's sum of squares is accumulated in `f32` and its root rounded to `bf16` once, so
+/// long rows lose far less than they would with `bf16` arithmetic. Rows whose squares overflow
+/// or underflow `f32` are summed again in `f64`, so they keep full accuracy too, as long as the
+/// norm itself fits in `bf16`.
+///
+/// # Arguments
+/// - `data`: The matrix in row-major order.
+/// - `cols`: The number of columns, e.g. the embedding dimension.
+///
+/// # Returns
+/// - `Ok(Vec<bf16>)` with one norm per row. Rows with an infinite entry have an infinite norm.
+/// - `Err(anyhow::Error)` if `data` does not split into rows of `cols` values, or a row contains
+///   `NaN`.
+///
+/// ```
+/// use half::bf16;
+///
+/// let embeddings = [3.0, 4.0, 0.0, 1.0, 1.0, 1.0].map(bf16::from_f32);
+/// let norms = sqrtx::row_norms_bf16(&embeddings, 3)?;
+/// assert_eq!(norms, [5.0, 1.734375].map(bf16::from_f32));
+/// # Ok::<(), sqrtx::Error>(())
+/// ```
+pub fn row_norms_bf16(data: &[bf16], cols: usize) -> Result<Vec<bf16>> {
+    check_shape(data.len(), cols)?;
+    Ok(data
+        .par_chunks(cols)
+        .map(row_norm)
+        .collect::<Result<_, _>>()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_square_roots_bf16_matches_scalar_exhaustively() -> Result<(), SqrtError> {
+        let numbers: Vec<bf16> = (0..0x7f80).chain([0x8000]).map(bf16::from_bits).collect();
+        let roots = square_roots_bf16(&numbers).expect("finite non-negative inputs have roots");
+        for (number, root) in numbers.into_iter().zip(roots) {
+            assert_eq!(root.to_bits(), number.kernel_sqrt()?.to_bits(), "√{number}");
+        }
+        assert!(square_roots_bf16(&[]).is_ok_and(|roots| roots.is_empty()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_square_roots_bf16_errors() {
+        let numbers = [4.0, -1.0, f32::NAN].map(bf16::from_f32);
+        let error = square_roots_bf16(&numbers).unwrap_err();
+        assert!(matches!(
+            crate::error::as_sqrt_error(&error),
+            Some(SqrtError::NegativeNumber(value)) if *value == -1.0
+        ));
+        for number in [bf16::NAN, bf16::INFINITY] {
+            let error = square_roots_bf16(&[bf16::ONE, number]).unwrap_err();
+            assert!(matches!(
+                crate::error::as_sqrt_error(&error),
+                Some(SqrtError::NonConvergence { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_row_norms_bf16() -> Result<()> {
+        // A 768-dimensional embedding of 0.01s: `bf16` accumulation would stall far below 0.277.
+        let row = vec![bf16::from_f32(0.01); 768];
+        let norm = row_norms_bf16(&row, 768)?[0];
+        let expected = (768.0 * f64::from(bf16::from_f32(0.01)).powi(2)).sqrt();
+        assert_eq!(norm, bf16::from_f64(expected));
+
+        // Rows whose squares leave the `f32` range still get accurate norms.
+        let large = bf16::from_f32(1e38);
+        let data = [large, large, bf16::MIN_POSITIVE_SUBNORMAL, bf16::ZERO];
+        let norms = row_norms_bf16(&data, 2)?;
+        assert_eq!(norms[0], bf16::from_f64(large.to_f64() * 2f64.sqrt()));
+        assert_eq!(norms[1], bf16::MIN_POSITIVE_SUBNORMAL);
+
+        assert_eq!(row_norms_bf16(&[bf16::ZERO; 4], 2)?, [bf16::ZERO; 2]);
+        assert_eq!(
+            row_norms_bf16(&[bf16::ONE, bf16::NEG_INFINITY], 1)?,
+            [bf16::ONE, bf16::INFINITY]
+        );
+        assert!(row_norms_bf16(&[bf16::ONE; 3], 2).is_err());
+        assert!(row_norms_bf16(&[bf16::ONE; 3], 0).is_err());
+        assert!(row_norms_bf16(&[bf16::ONE, bf16::NAN], 2).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_row_norms_bf16_match_f64() -> Result<()> {
+        let data: Vec<bf16> = crate::fixtures::random_bits(64 * 301)
+            .map(|bits| bf16::from_f64((bits >> 11) as f64 / (1u64 << 53) as f64 * 2.0 - 1.0))
+            .collect();
+        let norms = row_norms_bf16(&data, 301)?;
+        for (row, norm) in data.chunks(301).zip(norms) {
+            let expected = sum_sq_f64(row).sqrt();
+            // One `bf16` rounding, plus a little for the `f32` sum.
+            assert!((norm.to_f64() - expected).abs() <= expected * 2f64.powi(-8) * 1.01);
+        }
+        Ok(())
+    }
+}
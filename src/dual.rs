@@ -0,0 +1,87 @@
+use crate::{kernel, Result};
+
+/// A dual number `value + derivative·ε` with `ε² = 0`, for forward-mode differentiation.
+///
+/// Seed an input with [`Dual::variable`] and the derivative of a computation with respect to it
+/// is carried along with the value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Dual<T> {
+    /// The value of the expression.
+    pub value: T,
+    /// Its derivative with respect to the seeded variable.
+    pub derivative: T,
+}
+
+impl<T> Dual<T> {
+    /// Creates a dual number from a value and its derivative.
+    pub fn new(value: T, derivative: T) -> Self {
+        Self { value, derivative }
+    }
+}
+
+impl Dual<f64> {
+    /// The variable being differentiated against, whose derivative is one.
+    pub fn variable(value: f64) -> Self {
+        Self::new(value, 1.0)
+    }
+
+    /// A constant, whose derivative is zero.
+    pub fn constant(value: f64) -> Self {
+        Self::new(value, 0.0)
+    }
+}
+
+/// Computes the square root of a dual number, giving the root and its derivative together.
+///
+/// The value goes through the same kernel as [`square_root`](crate::square_root) and the
+/// derivative follows the chain rule, `x' / (2√x)`. At zero the derivative is infinite (with the
+/// sign of `x'`) unless `x'` is zero.
+///
+/// # Arguments
+/// - `x`: The input (its value must be non-negative).
+///
+/// # Returns
+/// - `Ok(Dual<f64>)` with `√x` and its derivative.
+/// - `Err(anyhow::Error)` if the value is negative or the iteration fails to converge.
+///
+/// ```
+/// use sqrtx::Dual;
+///
+/// let root = sqrtx::square_root_dual(Dual::variable(4.0))?;
+/// assert!((root.value - 2.0).abs() < 1e-10);
+/// assert!((root.derivative - 0.25).abs() < 1e-10);
+/// # Ok::<(), sqrtx::Error>(())
+/// ```
+pub fn square_root_dual(x: Dual<f64>) -> Result<Dual<f64>> {
+    let root = kernel::sqrt(x.value)?;
+    let derivative = if x.derivative == 0.0 {
+        0.0
+    } else {
+        x.derivative / (2.0 * root)
+    };
+    Ok(Dual::new(root, derivative))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_square_root_dual() -> Result<()> {
+        let root = square_root_dual(Dual::variable(2.0))?;
+        assert!((root.value - 2f64.sqrt()).abs() < 1e-10);
+        assert!((root.derivative - 0.5 / 2f64.sqrt()).abs() < 1e-10);
+
+        // Chain rule through an inner function with derivative 6: d/dt √(6t) at t = 1.5.
+        let root = square_root_dual(Dual::new(9.0, 6.0))?;
+        assert!((root.derivative - 1.0).abs() < 1e-10);
+
+        assert_eq!(square_root_dual(Dual::constant(16.0))?.derivative, 0.0);
+        assert_eq!(
+            square_root_dual(Dual::new(0.0, -1.0))?.derivative,
+            f64::NEG_INFINITY
+        );
+        assert!(square_root_dual(Dual::variable(-1.0)).is_err());
+        Ok(())
+    }
+}
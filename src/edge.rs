@@ -0,0 +1,168 @@
+use crate::batch;
+use crate::{BatchReport, SqrtError, SqrtOptions};
+
+/// Relative error, in units of `f64::EPSILON`, within which a root counts as correct.
+const EDGE_CASE_ULPS: f64 = 4.0;
+
+/// The curated hard inputs run by [`SqrtOptions::exercise_edge_cases`].
+const EDGE_CASES: [(&str, f64); 20] = [
+    ("zero", 0.0),
+    ("negative zero", -0.0),
+    ("one", 1.0),
+    ("just below one", 1.0 - f64::EPSILON / 2.0),
+    ("just above one", 1.0 + f64::EPSILON),
+    ("smallest subnormal", f64::from_bits(1)),
+    ("largest subnormal", f64::from_bits(0x000f_ffff_ffff_ffff)),
+    ("smallest normal", f64::MIN_POSITIVE),
+    ("f32 subnormal", 1e-40),
+    ("tiny", 1e-300),
+    ("below the tolerance", 1e-20),
+    ("large perfect square", 4_503_599_761_588_224.0),
+    ("largest exact integer", 9_007_199_254_740_992.0),
+    ("large", 1e300),
+    ("largest finite", f64::MAX),
+    ("negative", -1.0),
+    ("negative subnormal", -f64::from_bits(1)),
+    ("infinity", f64::INFINITY),
+    ("negative infinity", f64::NEG_INFINITY),
+    ("NaN", f64::NAN),
+];
+
+/// One input of an edge-case run and how the configuration handled it.
+#[derive(Debug)]
+pub struct EdgeCase {
+    /// Short description of the input, e.g. `"smallest subnormal"`.
+    pub name: &'static str,
+    /// The input.
+    pub input: f64,
+    /// The correctly rounded root of the input after flushing and clamping, or `None` if the
+    /// input must be rejected.
+    pub expected: Option<f64>,
+    /// What the configuration returned.
+    pub actual: Result<f64, SqrtError>,
+}
+
+impl EdgeCase {
+    /// Whether the input was rejected as expected, or its root is within a few ulps of the
+    /// correctly rounded root (with the sign of zero preserved).
+    pub fn passed(&self) -> bool {
+        match (self.expected, &self.actual) {
+            (None, actual) => actual.is_err(),
+            (Some(_), Err(_)) => false,
+            (Some(expected), Ok(actual)) if expected == 0.0 => {
+                actual.to_bits() == expected.to_bits()
+            }
+            (Some(expected), Ok(actual)) => {
+                (actual - expected).abs() <= expected * EDGE_CASE_ULPS * f64::EPSILON
+            }
+        }
+    }
+}
+
+/// Result of [`SqrtOptions::exercise_edge_cases`].
+#[derive(Debug)]
+pub struct EdgeCaseReport {
+    /// Every case that was run, in a fixed order.
+    pub cases: Vec<EdgeCase>,
+}
+
+impl EdgeCaseReport {
+    /// The cases that did not pass.
+    pub fn failures(&self) -> impl Iterator<Item = &EdgeCase> {
+        self.cases.iter().filter(|case| !case.passed())
+    }
+
+    /// Whether every case passed.
+    pub fn is_pass(&self) -> bool {
+        self.failures().next().is_none()
+    }
+}
+
+impl SqrtOptions {
+    /// Runs these options against a fixed suite of hard inputs and reports which were handled
+    /// correctly, e.g. to gate a deployment on a build's feature set and configuration.
+    ///
+    /// The suite covers signed zeros, subnormals, values near one, large integers, values near
+    /// overflow and the inputs that must be rejected. Each input is run as its own one-element
+    /// batch through the same path as the batch APIs, including flushing and the backend, and
+    /// compared with the correctly rounded root.
+    ///
+    /// # Returns
+    /// - `EdgeCaseReport` with one entry per input.
+    ///
+    /// ```
+    /// let report = sqrtx::SqrtOptions::new().exercise_edge_cases();
+    /// for case in report.failures() {
+    ///     println!("{}: expected {:?}, got {:?}", case.name, case.expected, case.actual);
+    /// }
+    /// ```
+    pub fn exercise_edge_cases(&self) -> EdgeCaseReport {
+        let cases = EDGE_CASES
+            .iter()
+            .map(|&(name, input)| {
+                let flushed = if self.flush_denormals && input.is_subnormal() {
+                    0.0_f64.copysign(input)
+                } else {
+                    input
+                };
+                let valid = flushed.is_finite() && (flushed >= 0.0);
+                EdgeCase {
+                    name,
+                    input,
+                    expected: valid
+                        .then(|| batch::clamp(flushed.sqrt(), self, &mut BatchReport::default())),
+                    actual: batch::run_batch_cancellable(&[input], self, None)
+                        .map(|(roots, _)| roots[0]),
+                }
+            })
+            .collect();
+        EdgeCaseReport { cases }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edge_cases_reject_invalid_inputs() {
+        for options in [SqrtOptions::new(), SqrtOptions::new().flush_denormals(true)] {
+            let report = options.exercise_edge_cases();
+            assert_eq!(report.cases.len(), EDGE_CASES.len());
+            for name in [
+                "zero",
+                "negative zero",
+                "one",
+                "negative",
+                "infinity",
+                "NaN",
+            ] {
+                let case = report.cases.iter().find(|case| case.name == name).unwrap();
+                assert!(case.passed(), "{case:?}");
+            }
+            assert_eq!(report.is_pass(), report.failures().count() == 0);
+        }
+    }
+
+    #[test]
+    fn test_edge_cases_follow_flushing() {
+        let report = SqrtOptions::new()
+            .flush_denormals(true)
+            .exercise_edge_cases();
+        let case = report
+            .cases
+            .iter()
+            .find(|case| case.name == "negative subnormal")
+            .unwrap();
+        assert_eq!(case.expected.map(f64::to_bits), Some((-0.0_f64).to_bits()));
+        assert!(case.passed(), "{case:?}");
+    }
+
+    #[cfg(any(feature = "soft-float", feature = "hardware"))]
+    #[test]
+    fn test_correctly_rounded_kernels_pass() {
+        let report = SqrtOptions::new().exercise_edge_cases();
+        let failures: Vec<_> = report.failures().collect();
+        assert!(failures.is_empty(), "{failures:?}");
+    }
+}
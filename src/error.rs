@@ -1,7 +1,298 @@
-use thiserror::Error;
+use std::time::Duration;
 
-#[derive(Debug, Error)]
+/// Error type of the public APIs: [`anyhow::Error`] with the default `anyhow` feature, so any
+/// underlying error can be downcast, or [`SqrtError`] without it.
+#[cfg(feature = "anyhow")]
+pub type Error = anyhow::Error;
+/// Error type of the public APIs: [`anyhow::Error`] with the default `anyhow` feature, so any
+/// underlying error can be downcast, or [`SqrtError`] without it.
+#[cfg(not(feature = "anyhow"))]
+pub type Error = SqrtError;
+
+/// Result type of the public APIs, using the crate's [`Error`] by default.
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Wraps an error from outside the crate (e.g. a [`ResultSink`](crate::ResultSink)) in the
+/// crate's [`Error`].
+pub(crate) fn external<E: std::error::Error + Send + Sync + 'static>(error: E) -> Error {
+    #[cfg(feature = "anyhow")]
+    return anyhow::Error::new(error);
+
+    #[cfg(not(feature = "anyhow"))]
+    SqrtError::External(Box::new(error))
+}
+
+/// Returns the [`SqrtError`] behind a crate [`Error`], if there is one.
+#[cfg(test)]
+pub(crate) fn as_sqrt_error(error: &Error) -> Option<&SqrtError> {
+    #[cfg(feature = "anyhow")]
+    return SqrtError::from_anyhow_ref(error);
+
+    #[cfg(not(feature = "anyhow"))]
+    Some(error)
+}
+
+/// Takes the [`SqrtError`] out of a crate [`Error`], if there is one.
+#[cfg(all(test, feature = "nalgebra"))]
+pub(crate) fn into_sqrt_error(error: Error) -> Option<SqrtError> {
+    #[cfg(feature = "anyhow")]
+    return SqrtError::from_anyhow(error).ok();
+
+    #[cfg(not(feature = "anyhow"))]
+    Some(error)
+}
+
+/// Errors reported by the crate.
+///
+/// With the `tiny` feature the messages are compiled out and every variant displays as its
+/// [`code`](SqrtError::code) instead, e.g. `sqrtx error 1`.
+#[derive(Debug, thiserror::Error)]
+#[cfg_attr(feature = "tiny", error("sqrtx error {}", self.code()))]
 pub enum SqrtError {
-    #[error("Cannot calculate the square root of a negative number: {0}")]
+    #[cfg_attr(
+        not(feature = "tiny"),
+        error("Cannot calculate the square root of a negative number: {0}")
+    )]
     NegativeNumber(f64),
+    #[cfg_attr(
+        not(feature = "tiny"),
+        error(
+            "Root iteration did not converge after {iterations} iterations (residual: {residual})"
+        )
+    )]
+    NonConvergence { iterations: u32, residual: f64 },
+    #[cfg_attr(
+        not(feature = "tiny"),
+        error("Root degree must be at least 1, got {0}")
+    )]
+    InvalidDegree(u32),
+    #[cfg_attr(
+        not(feature = "tiny"),
+        error("Cannot normalize a vector with zero norm")
+    )]
+    ZeroNorm,
+    #[cfg_attr(
+        not(feature = "tiny"),
+        error("Batch of at least {elements} elements exceeds the limit of {limit}")
+    )]
+    BatchTooLarge { elements: usize, limit: usize },
+    #[cfg_attr(
+        not(feature = "tiny"),
+        error(
+            "Blocking pool saturated: batch of {elements} elements waited longer than {waited:?}"
+        )
+    )]
+    Overloaded { elements: usize, waited: Duration },
+    #[cfg_attr(
+        not(feature = "tiny"),
+        error("Matrix must be square, got {rows}x{cols}")
+    )]
+    NotSquare { rows: usize, cols: usize },
+    #[cfg_attr(not(feature = "tiny"), error("Matrix is not symmetric"))]
+    NotSymmetric,
+    #[cfg_attr(not(feature = "tiny"), error("Matrix is not positive definite"))]
+    NotPositiveDefinite,
+    #[cfg_attr(
+        not(feature = "tiny"),
+        error("Not enough data: needed at least {needed} values, got {got}")
+    )]
+    InsufficientData { needed: usize, got: usize },
+    #[cfg_attr(
+        not(feature = "tiny"),
+        error("Confidence level must be strictly between 0 and 1, got {0}")
+    )]
+    InvalidConfidence(f64),
+    #[cfg_attr(
+        not(feature = "tiny"),
+        error("Buffer of {len} values does not split into rows of {cols} columns")
+    )]
+    ShapeMismatch { len: usize, cols: usize },
+    /// `partial` holds the roots computed before the deadline, with `NaN` for skipped inputs. It
+    /// is empty for the single-pass helpers such as [`sqrt_filter_with`](crate::sqrt_filter_with),
+    /// which never keep every root.
+    #[cfg_attr(
+        not(feature = "tiny"),
+        error("Deadline exceeded: computed {completed} of {elements} values")
+    )]
+    DeadlineExceeded {
+        completed: usize,
+        elements: usize,
+        partial: Vec<f64>,
+    },
+    #[cfg_attr(not(feature = "tiny"), error("Histogram needs at least one bin over a finite range, got {bins} bins over [{low}, {high}]"))]
+    InvalidHistogram { low: f64, high: f64, bins: usize },
+    #[cfg_attr(
+        not(feature = "tiny"),
+        error("Square root of {0} is out of range for the fixed-point type")
+    )]
+    FixedPointOverflow(f64),
+    #[cfg_attr(
+        not(feature = "tiny"),
+        error("Interval lower bound {lo} must not exceed its upper bound {hi}")
+    )]
+    InvalidInterval { lo: f64, hi: f64 },
+    #[cfg_attr(not(feature = "tiny"), error("Invalid decimal number: {0:?}"))]
+    InvalidDecimal(String),
+    #[cfg_attr(not(feature = "tiny"), error("Invalid options blob: {0}"))]
+    InvalidOptionsBlob(&'static str),
+    #[cfg(feature = "async")]
+    #[cfg_attr(not(feature = "tiny"), error("Background task failed: {0}"))]
+    TaskFailed(#[from] tokio::task::JoinError),
+    #[cfg(feature = "async")]
+    #[cfg_attr(
+        not(feature = "tiny"),
+        error("Worker dropped the batch without returning a result")
+    )]
+    WorkerLost(#[from] futures::channel::oneshot::Canceled),
+    #[error(transparent)]
+    External(Box<dyn std::error::Error + Send + Sync>),
+    #[cfg_attr(
+        not(feature = "tiny"),
+        error("Computation cancelled after {completed} of {elements} values")
+    )]
+    Cancelled { completed: usize, elements: usize },
+    /// The iteration was still making progress when it hit the configured
+    /// [`max_iterations`](crate::SqrtOptions::max_iterations), unlike
+    /// [`SqrtError::NonConvergence`], which means it stalled or the input was not finite.
+    #[cfg_attr(
+        not(feature = "tiny"),
+        error("Square root iteration hit the cap of {iterations} iterations (residual: {last_residual})")
+    )]
+    DidNotConverge { iterations: u32, last_residual: f64 },
+    #[cfg_attr(
+        not(feature = "tiny"),
+        error("Quantization scale must be positive and finite, got {0}")
+    )]
+    InvalidScale(f64),
+}
+
+impl SqrtError {
+    /// Stable numeric code of the error kind, for logs and wire formats that should not depend
+    /// on message wording. Codes are never reused, including for variants behind a disabled
+    /// feature.
+    pub fn code(&self) -> u16 {
+        match self {
+            Self::NegativeNumber(_) => 1,
+            Self::NonConvergence { .. } => 2,
+            Self::InvalidDegree(_) => 3,
+            Self::ZeroNorm => 4,
+            Self::BatchTooLarge { .. } => 5,
+            Self::Overloaded { .. } => 6,
+            Self::NotSquare { .. } => 7,
+            Self::NotSymmetric => 8,
+            Self::NotPositiveDefinite => 9,
+            Self::InsufficientData { .. } => 10,
+            Self::InvalidConfidence(_) => 11,
+            Self::ShapeMismatch { .. } => 12,
+            Self::DeadlineExceeded { .. } => 13,
+            Self::InvalidHistogram { .. } => 14,
+            Self::FixedPointOverflow(_) => 15,
+            Self::InvalidInterval { .. } => 16,
+            Self::InvalidDecimal(_) => 17,
+            Self::InvalidOptionsBlob(_) => 18,
+            #[cfg(feature = "async")]
+            Self::TaskFailed(_) => 19,
+            #[cfg(feature = "async")]
+            Self::WorkerLost(_) => 20,
+            Self::External(_) => 21,
+            Self::Cancelled { .. } => 22,
+            Self::DidNotConverge { .. } => 23,
+            Self::InvalidScale(_) => 24,
+        }
+    }
+}
+
+#[cfg(feature = "anyhow")]
+impl SqrtError {
+    /// Recovers the [`SqrtError`] carried by an [`anyhow::Error`], e.g. one returned by a crate
+    /// API and since wrapped with [`anyhow::Context`].
+    ///
+    /// # Returns
+    /// - `Ok(SqrtError)` if the error, or the error under its context, is a [`SqrtError`].
+    /// - `Err(anyhow::Error)` with the original error otherwise.
+    pub fn from_anyhow(error: anyhow::Error) -> Result<Self, anyhow::Error> {
+        error.downcast()
+    }
+
+    /// Borrowing form of [`SqrtError::from_anyhow`].
+    pub fn from_anyhow_ref(error: &anyhow::Error) -> Option<&Self> {
+        error.downcast_ref()
+    }
+}
+
+#[cfg(all(test, feature = "anyhow"))]
+mod tests {
+    use anyhow::Context;
+
+    use super::*;
+
+    fn fails() -> anyhow::Result<f64> {
+        Ok(crate::kernel::sqrt(-4.0)?)
+    }
+
+    #[test]
+    fn test_round_trip_through_anyhow() {
+        let error: anyhow::Error = SqrtError::InvalidDegree(0).into();
+        assert!(matches!(
+            SqrtError::from_anyhow_ref(&error),
+            Some(SqrtError::InvalidDegree(0))
+        ));
+        assert!(matches!(
+            SqrtError::from_anyhow(error),
+            Ok(SqrtError::InvalidDegree(0))
+        ));
+
+        let error = fails().context("computing the side length").unwrap_err();
+        assert_eq!(error.to_string(), "computing the side length");
+        assert!(matches!(
+            SqrtError::from_anyhow_ref(&error),
+            Some(SqrtError::NegativeNumber(value)) if *value == -4.0
+        ));
+        assert!(matches!(
+            SqrtError::from_anyhow(error),
+            Ok(SqrtError::NegativeNumber(_))
+        ));
+    }
+
+    #[test]
+    fn test_foreign_error_is_returned_unchanged() {
+        let error = anyhow::anyhow!("not ours");
+        assert!(SqrtError::from_anyhow_ref(&error).is_none());
+        let error = SqrtError::from_anyhow(error).unwrap_err();
+        assert_eq!(error.to_string(), "not ours");
+    }
+}
+
+#[cfg(test)]
+mod tests_code {
+    use super::*;
+
+    #[test]
+    fn test_codes_are_distinct() {
+        let errors = [
+            SqrtError::NegativeNumber(-1.0),
+            SqrtError::ZeroNorm,
+            SqrtError::NotSquare { rows: 1, cols: 2 },
+            SqrtError::Cancelled {
+                completed: 0,
+                elements: 1,
+            },
+            SqrtError::InvalidScale(0.0),
+        ];
+        let codes: Vec<u16> = errors.iter().map(SqrtError::code).collect();
+        assert_eq!(codes, [1, 4, 7, 22, 24]);
+    }
+
+    #[test]
+    fn test_display() {
+        let error = SqrtError::NegativeNumber(-4.0);
+        if cfg!(feature = "tiny") {
+            assert_eq!(error.to_string(), "sqrtx error 1");
+        } else {
+            assert_eq!(
+                error.to_string(),
+                "Cannot calculate the square root of a negative number: -4"
+            );
+        }
+    }
 }
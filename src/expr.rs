@@ -0,0 +1,159 @@
+use std::ops::{Add, Div, Mul, Sub};
+
+use crate::Result;
+
+use crate::kernel::{self, newton_nth_root};
+
+/// A single elementwise step of an [`Expr`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Sqrt,
+    NthRoot(u32),
+    Add(f64),
+    Mul(f64),
+}
+
+/// A lazily built elementwise expression that is evaluated in a single pass over the data.
+///
+/// Composing `sqrt`, roots and scalar arithmetic only records the steps; [`Expr::apply`] then
+/// runs all of them on each element before moving on to the next one, instead of sweeping the
+/// whole buffer once per operation:
+///
+/// ```
+/// use sqrtx::Expr;
+///
+/// let expr = Expr::col().sqrt() * 2.0 + 1.0;
+/// assert_eq!(expr.apply(&[4.0, 9.0])?, vec![5.0, 7.0]);
+/// # Ok::<(), sqrtx::Error>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Expr {
+    ops: Vec<Op>,
+}
+
+impl Expr {
+    /// The identity expression, referring to the input column itself.
+    pub fn col() -> Self {
+        Self::default()
+    }
+
+    /// Takes the square root of the current value.
+    pub fn sqrt(self) -> Self {
+        self.push(Op::Sqrt)
+    }
+
+    /// Takes the real `degree`-th root of the current value, see [`nth_root`](crate::nth_root).
+    pub fn nth_root(self, degree: u32) -> Self {
+        self.push(Op::NthRoot(degree))
+    }
+
+    fn push(mut self, op: Op) -> Self {
+        // Fold consecutive scalar steps so the fused loop does as little work as possible.
+        match (self.ops.last_mut(), op) {
+            (Some(Op::Add(a)), Op::Add(b)) => *a += b,
+            (Some(Op::Mul(a)), Op::Mul(b)) => *a *= b,
+            _ => self.ops.push(op),
+        }
+        self
+    }
+
+    /// Evaluates the expression for a single value.
+    ///
+    /// # Returns
+    /// - `Ok(f64)` if every step is successful.
+    /// - `Err(anyhow::Error)` if a root step receives a negative value or fails to converge.
+    pub fn eval(&self, value: f64) -> Result<f64> {
+        self.ops.iter().try_fold(value, |value, op| {
+            Ok(match *op {
+                Op::Sqrt => kernel::sqrt(value)?,
+                Op::NthRoot(degree) => newton_nth_root(value, degree)?,
+                Op::Add(rhs) => value + rhs,
+                Op::Mul(rhs) => value * rhs,
+            })
+        })
+    }
+
+    /// Evaluates the expression for every element of `data` in one pass.
+    ///
+    /// # Returns
+    /// - `Ok(Vec<f64>)` if every step is successful.
+    /// - `Err(anyhow::Error)` if a root step receives a negative value or fails to converge.
+    pub fn apply(&self, data: &[f64]) -> Result<Vec<f64>> {
+        data.iter().map(|&value| self.eval(value)).collect()
+    }
+
+    /// Evaluates the expression for every element of `data` in place.
+    ///
+    /// On error, elements before the failing one have already been overwritten.
+    pub fn apply_in_place(&self, data: &mut [f64]) -> Result<()> {
+        for value in data {
+            *value = self.eval(*value)?;
+        }
+        Ok(())
+    }
+}
+
+impl Add<f64> for Expr {
+    type Output = Expr;
+
+    fn add(self, rhs: f64) -> Expr {
+        self.push(Op::Add(rhs))
+    }
+}
+
+impl Sub<f64> for Expr {
+    type Output = Expr;
+
+    fn sub(self, rhs: f64) -> Expr {
+        self.push(Op::Add(-rhs))
+    }
+}
+
+impl Mul<f64> for Expr {
+    type Output = Expr;
+
+    fn mul(self, rhs: f64) -> Expr {
+        self.push(Op::Mul(rhs))
+    }
+}
+
+impl Div<f64> for Expr {
+    type Output = Expr;
+
+    fn div(self, rhs: f64) -> Expr {
+        self.push(Op::Mul(rhs.recip()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fused_expression() -> Result<()> {
+        let expr = (Expr::col() + 1.0 + 2.0)
+            .nth_root(3)
+            .sqrt()
+            .mul(4.0)
+            .div(2.0)
+            - 1.0;
+        assert_eq!(expr.ops.len(), 5);
+
+        let mut data = [5.0, 61.0];
+        expr.apply_in_place(&mut data)?;
+        assert!((data[0] - (2f64.sqrt() * 2.0 - 1.0)).abs() < 1e-10);
+        assert!((data[1] - 3.0).abs() < 1e-10);
+        Ok(())
+    }
+
+    #[test]
+    #[cfg_attr(feature = "tiny", ignore = "checks the message text")]
+    fn test_fused_expression_negative() {
+        let expr = (Expr::col() - 10.0).sqrt();
+        let error = expr.apply(&[16.0, 4.0]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Cannot calculate the square root of a negative number: -6"
+        );
+    }
+}
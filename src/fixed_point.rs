@@ -0,0 +1,95 @@
+use fixed::types::extra::{LeEqU16, LeEqU32, LeEqU64};
+use fixed::{FixedI16, FixedI32, FixedI64, FixedU16, FixedU32, FixedU64};
+
+use crate::SqrtError;
+
+/// Square roots of [`fixed`] numbers using integer arithmetic only, for targets without an FPU.
+///
+/// A value with `f` fractional bits stores `x · 2^f`, so its root's bits are `⌊√(bits · 2^f)⌋`.
+/// That integer square root is computed exactly in the next wider unsigned type, so the result is
+/// the representable value just at or below the exact root.
+///
+/// ```
+/// use fixed::types::I16F16;
+/// use sqrtx::FixedSqrt;
+///
+/// let root = I16F16::from_num(2).fixed_sqrt()?;
+/// assert_eq!(root, I16F16::from_bits(92_681));
+/// # Ok::<(), sqrtx::SqrtError>(())
+/// ```
+pub trait FixedSqrt: Sized {
+    /// Returns the square root, truncated to the type's precision.
+    ///
+    /// # Returns
+    /// - `Ok(Self)` with the root.
+    /// - `Err(SqrtError::NegativeNumber)` if `self` is negative.
+    /// - `Err(SqrtError::FixedPointOverflow)` if the root exceeds the type's range, which only
+    ///   happens for signed types with no integer bits.
+    fn fixed_sqrt(self) -> Result<Self, SqrtError>;
+}
+
+macro_rules! impl_fixed_sqrt {
+    ($($fixed:ident<$frac:ident>: $bits:ty => $unsigned:ty => $wide:ty;)*) => {$(
+        impl<Frac: $frac> FixedSqrt for $fixed<Frac> {
+            fn fixed_sqrt(self) -> Result<Self, SqrtError> {
+                if self < Self::ZERO {
+                    return Err(SqrtError::NegativeNumber(self.to_num()));
+                }
+                let radicand = <$wide>::from(self.to_bits() as $unsigned) << Self::FRAC_NBITS;
+                <$bits>::try_from(radicand.isqrt())
+                    .map(Self::from_bits)
+                    .map_err(|_| SqrtError::FixedPointOverflow(self.to_num()))
+            }
+        }
+    )*};
+}
+
+impl_fixed_sqrt! {
+    FixedU16<LeEqU16>: u16 => u16 => u32;
+    FixedI16<LeEqU16>: i16 => u16 => u32;
+    FixedU32<LeEqU32>: u32 => u32 => u64;
+    FixedI32<LeEqU32>: i32 => u32 => u64;
+    FixedU64<LeEqU64>: u64 => u64 => u128;
+    FixedI64<LeEqU64>: i64 => u64 => u128;
+}
+
+#[cfg(test)]
+mod tests {
+    use fixed::types::extra::U32;
+    use fixed::types::{I16F16, I32F32, I8F8, U0F16, U32F32};
+
+    use super::*;
+
+    #[test]
+    fn test_fixed_sqrt_truncates() -> Result<(), SqrtError> {
+        for value in [0.0, 1.0, 2.0, 0.5, 1234.5678, 32767.0] {
+            let root = I16F16::from_num(value).fixed_sqrt()?;
+            let exact = I16F16::from_num(value).to_num::<f64>().sqrt();
+            let ulp = I16F16::DELTA.to_num::<f64>();
+            let error = exact - root.to_num::<f64>();
+            assert!((0.0..ulp).contains(&error), "√{value}: {root}");
+        }
+        assert_eq!(I8F8::from_num(16).fixed_sqrt()?, I8F8::from_num(4));
+        assert_eq!(U0F16::MAX.fixed_sqrt()?, U0F16::MAX);
+        assert_eq!(U32F32::MAX.fixed_sqrt()?.to_num::<u32>(), u16::MAX as u32);
+
+        let root = I32F32::from_num(1e9).fixed_sqrt()?;
+        assert!((root.to_num::<f64>() - 1e9f64.sqrt()).abs() < 1e-9);
+        Ok(())
+    }
+
+    #[test]
+    fn test_fixed_sqrt_errors() {
+        assert!(matches!(
+            I16F16::from_num(-2).fixed_sqrt(),
+            Err(SqrtError::NegativeNumber(value)) if value == -2.0
+        ));
+        // No integer bits: the range is [-0.5, 0.5), but √0.25 = 0.5.
+        let quarter = FixedI32::<U32>::from_num(0.25);
+        assert!(matches!(
+            quarter.fixed_sqrt(),
+            Err(SqrtError::FixedPointOverflow(value)) if value == 0.25
+        ));
+        assert!(FixedI32::<U32>::from_num(0.2).fixed_sqrt().is_ok());
+    }
+}
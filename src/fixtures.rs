@@ -0,0 +1,156 @@
+//! Reproducible input datasets for benchmarks and regression tests.
+//!
+//! Every dataset is derived from a fixed SplitMix64 stream, so the same profile, length and
+//! seed give bit-identical values on every platform and release.
+
+/// Seed used by [`generate`].
+pub const DEFAULT_SEED: u64 = 0x5157_5254_0000_0001;
+
+/// Shape of a generated dataset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Profile {
+    /// Uniform over `[0, 1e6)`.
+    Uniform,
+    /// Log-uniform over `[2^-996, 2^996)` (about `1e-300` to `1e300`), exercising the full
+    /// exponent range.
+    LogUniform,
+    /// Tight clusters (±0.1%) around 16 log-uniform centres in `[2^-20, 2^20)`.
+    Clustered,
+    /// Perfect squares `k²` and their neighbouring `f64` values, where a root that is off by one
+    /// ulp is easiest to spot.
+    NearPerfectSquares,
+}
+
+/// The SplitMix64 generator.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `[0, 1)`, with all 53 bits of precision.
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Log-uniform in `[2^-bound, 2^bound)`: a uniform binade with a uniform significand.
+    ///
+    /// Built from the bit pattern rather than with `powf`, whose last bit varies between libms.
+    fn next_log_uniform(&mut self, bound: u64) -> f64 {
+        let exponent = 1023 - bound + self.next_u64() % (2 * bound);
+        f64::from_bits(exponent << 52 | self.next_u64() >> 12)
+    }
+}
+
+/// Generates `n` non-negative inputs of the given profile from [`DEFAULT_SEED`].
+///
+/// ```
+/// use sqrtx::fixtures::{generate, Profile};
+///
+/// let inputs = generate(Profile::LogUniform, 1000);
+/// assert_eq!(inputs, generate(Profile::LogUniform, 1000));
+/// assert!(inputs.iter().all(|&x| x > 0.0 && x.is_finite()));
+/// ```
+pub fn generate(profile: Profile, n: usize) -> Vec<f64> {
+    generate_with_seed(profile, n, DEFAULT_SEED)
+}
+
+/// Generates `n` non-negative inputs of the given profile from `seed`.
+///
+/// # Arguments
+/// - `profile`: The shape of the dataset.
+/// - `n`: The number of values.
+/// - `seed`: Selects one of the reproducible streams; equal seeds give equal datasets.
+///
+/// # Returns
+/// - `Vec<f64>` with `n` finite, non-negative values.
+pub fn generate_with_seed(profile: Profile, n: usize, seed: u64) -> Vec<f64> {
+    let mut rng = SplitMix64(seed);
+
+    match profile {
+        Profile::Uniform => (0..n).map(|_| 1e6 * rng.next_f64()).collect(),
+        Profile::LogUniform => (0..n).map(|_| rng.next_log_uniform(996)).collect(),
+        Profile::Clustered => {
+            let centres: Vec<f64> = (0..16).map(|_| rng.next_log_uniform(20)).collect();
+            (0..n)
+                .map(|_| {
+                    let centre = centres[(rng.next_u64() % 16) as usize];
+                    centre * (1.0 + 2e-3 * (rng.next_f64() - 0.5))
+                })
+                .collect()
+        }
+        Profile::NearPerfectSquares => (0..n)
+            .map(|_| {
+                // k < 2^26 keeps k² exact in an f64.
+                let k = (rng.next_u64() >> 38) as f64;
+                let square = k * k;
+                match rng.next_u64() % 3 {
+                    0 => square,
+                    1 => square.next_up(),
+                    _ => square.next_down().max(0.0),
+                }
+            })
+            .collect(),
+    }
+}
+
+/// Yields `n` reproducible pseudo-random `u64`s from [`DEFAULT_SEED`], for tests that need
+/// arbitrary `f64` bit patterns, e.g. `f64::from_bits(bits >> 1)` for non-negative values.
+#[cfg(test)]
+pub(crate) fn random_bits(n: usize) -> impl Iterator<Item = u64> {
+    let mut rng = SplitMix64(DEFAULT_SEED);
+    (0..n).map(move |_| rng.next_u64())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROFILES: [Profile; 4] = [
+        Profile::Uniform,
+        Profile::LogUniform,
+        Profile::Clustered,
+        Profile::NearPerfectSquares,
+    ];
+
+    #[test]
+    fn test_generate_is_reproducible() {
+        for profile in PROFILES {
+            let values = generate(profile, 2000);
+            assert_eq!(values.len(), 2000);
+            assert!(
+                values.iter().all(|&x| x >= 0.0 && x.is_finite()),
+                "{profile:?}"
+            );
+            assert_eq!(values, generate(profile, 2000));
+            assert_eq!(values[..100], generate(profile, 100)[..]);
+            assert_ne!(values, generate_with_seed(profile, 2000, 7));
+        }
+        // Pin the stream so a change to the generator is caught.
+        assert_eq!(SplitMix64(0).next_u64(), 0xe220_a839_7b1d_cdaf);
+    }
+
+    #[test]
+    fn test_generate_profiles() {
+        let log = generate(Profile::LogUniform, 10_000);
+        assert!(log.iter().any(|&x| x < 1e-200) && log.iter().any(|&x| x > 1e200));
+
+        let clustered = generate(Profile::Clustered, 10_000);
+        let mut sorted = clustered.clone();
+        sorted.sort_by(f64::total_cmp);
+        let gaps = sorted.windows(2).filter(|pair| pair[1] > pair[0] * 1.01);
+        assert!(gaps.count() < 16);
+
+        let near = generate(Profile::NearPerfectSquares, 10_000);
+        let exact = near
+            .iter()
+            .filter(|&&x| x as u64 as f64 == x && (x as u64).isqrt().pow(2) == x as u64)
+            .count();
+        assert!(exact > 2000 && exact < 5000, "{exact} perfect squares");
+    }
+}
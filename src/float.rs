@@ -0,0 +1,275 @@
+use std::fmt::Debug;
+use std::ops::{Add, Div, Mul, Sub};
+
+#[cfg(feature = "half")]
+use half::{bf16, f16};
+#[cfg(feature = "posit")]
+use softposit::{P16E1, P32E2};
+
+#[cfg(feature = "half")]
+use crate::mixed;
+use crate::{kernel, SqrtError};
+
+/// Floating-point types accepted by the generic square root APIs, such as
+/// [`square_root`](crate::square_root).
+///
+/// Implemented for `f32` and `f64`, for `half::f16` and `half::bf16` with the `half` feature,
+/// and for the `softposit::P16E1` and `softposit::P32E2` posits with the `posit` feature.
+pub trait SqrtFloat:
+    Copy
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Into<f64>
+    + Debug
+    + Send
+    + Sync
+    + 'static
+{
+    /// Zero.
+    const ZERO: Self;
+    /// Two.
+    const TWO: Self;
+    /// Positive infinity.
+    const INFINITY: Self;
+    /// Difference between successive Newton–Raphson guesses below which the iteration stops.
+    ///
+    /// `f64` uses the historical cutoff of `1e-10`. Newton's method converges quadratically, so
+    /// other types scale it by `√(ε / ε_f64)` to stop at the same number of correct bits
+    /// relative to their precision.
+    const TOLERANCE: Self;
+
+    /// Absolute value.
+    fn abs(self) -> Self;
+
+    /// Whether the value is `NaN`.
+    fn is_nan(self) -> bool;
+
+    /// Starting guess of the Newton–Raphson iteration for a positive `self`.
+    ///
+    /// Defaults to `self / 2`, which takes many steps to come down to the root of a large input.
+    /// `f32` and `f64` halve the exponent on the bit pattern instead.
+    fn initial_guess(self) -> Self {
+        self / Self::TWO
+    }
+
+    /// Computes the square root with the kernel selected for this type.
+    ///
+    /// Defaults to the generic Newton–Raphson iteration.
+    fn kernel_sqrt(self) -> Result<Self, SqrtError> {
+        kernel::newton_sqrt(self)
+    }
+}
+
+impl SqrtFloat for f64 {
+    const ZERO: Self = 0.0;
+    const TWO: Self = 2.0;
+    const INFINITY: Self = f64::INFINITY;
+    const TOLERANCE: Self = 1e-10;
+
+    fn abs(self) -> Self {
+        f64::abs(self)
+    }
+
+    fn is_nan(self) -> bool {
+        f64::is_nan(self)
+    }
+
+    /// Halving the biased exponent field on the bit pattern roughly halves the exponent of the
+    /// root; the constant restores the bias and is tuned so that every normal input starts
+    /// within 5% of its root, leaving Newton–Raphson about four steps for any magnitude.
+    fn initial_guess(self) -> Self {
+        f64::from_bits((self.to_bits() >> 1) + 0x1ff7_a3be_a000_0000)
+    }
+
+    fn kernel_sqrt(self) -> Result<Self, SqrtError> {
+        kernel::sqrt(self)
+    }
+}
+
+impl SqrtFloat for f32 {
+    const ZERO: Self = 0.0;
+    const TWO: Self = 2.0;
+    const INFINITY: Self = f32::INFINITY;
+    // 1e-10 · √(2^29), as ε_f32 / ε_f64 = 2^29.
+    const TOLERANCE: Self = 2.317e-6;
+
+    fn abs(self) -> Self {
+        f32::abs(self)
+    }
+
+    fn is_nan(self) -> bool {
+        f32::is_nan(self)
+    }
+
+    /// The `f32` counterpart of the `f64` exponent-halving guess.
+    fn initial_guess(self) -> Self {
+        f32::from_bits((self.to_bits() >> 1) + 0x1fbd_1df5)
+    }
+
+    /// With `soft-float` or `hardware`, computes the correctly rounded `f64` root and rounds it,
+    /// which is also correctly rounded for `f32`.
+    #[cfg(any(feature = "soft-float", feature = "hardware"))]
+    fn kernel_sqrt(self) -> Result<Self, SqrtError> {
+        kernel::sqrt(f64::from(self)).map(|root| root as f32)
+    }
+}
+
+/// Half-precision roots are computed as correctly rounded `f32` roots and rounded again. `f32`
+/// has more than twice the precision plus two bits of either type, so the double rounding is
+/// innocuous and the result is the correctly rounded half-precision root.
+macro_rules! impl_sqrt_float_half {
+    ($($t:ident: $tolerance:literal, $comment:literal;)*) => {$(
+        #[cfg(feature = "half")]
+        impl SqrtFloat for $t {
+            const ZERO: Self = $t::ZERO;
+            const TWO: Self = $t::from_f32_const(2.0);
+            const INFINITY: Self = $t::INFINITY;
+            #[doc = $comment]
+            const TOLERANCE: Self = $t::from_f32_const($tolerance);
+
+            fn abs(self) -> Self {
+                $t::from_bits(self.to_bits() & 0x7fff)
+            }
+
+            fn is_nan(self) -> bool {
+                $t::is_nan(self)
+            }
+
+            fn kernel_sqrt(self) -> Result<Self, SqrtError> {
+                mixed::rounded_sqrt_f32(self.to_f32()).map($t::from_f32)
+            }
+        }
+    )*};
+}
+
+impl_sqrt_float_half! {
+    f16: 2.097e-4, "1e-10 · √(2^42), as ε_f16 / ε_f64 = 2^42.";
+    bf16: 5.931e-4, "1e-10 · √(2^45), as ε_bf16 / ε_f64 = 2^45.";
+}
+
+/// Posit roots use softposit's correctly rounded square root, with the float paths' errors:
+/// negative posits fail with [`SqrtError::NegativeNumber`] and NaR, the posit counterpart of
+/// `NaN` and `±∞`, with [`SqrtError::NonConvergence`]. Posits have no infinity, so
+/// `INFINITY` is NaR as in softposit.
+macro_rules! impl_sqrt_float_posit {
+    ($($t:ident: $tolerance:literal, $comment:literal;)*) => {$(
+        #[cfg(feature = "posit")]
+        impl SqrtFloat for $t {
+            const ZERO: Self = $t::ZERO;
+            const TWO: Self = $t::from_f64(2.0);
+            const INFINITY: Self = $t::NAR;
+            #[doc = $comment]
+            const TOLERANCE: Self = $t::from_f64($tolerance);
+
+            fn abs(self) -> Self {
+                $t::abs(self)
+            }
+
+            fn is_nan(self) -> bool {
+                $t::is_nar(self)
+            }
+
+            fn kernel_sqrt(self) -> Result<Self, SqrtError> {
+                if self.is_nar() {
+                    return Err(SqrtError::NonConvergence {
+                        iterations: 1,
+                        residual: f64::NAN,
+                    });
+                }
+                if self < $t::ZERO {
+                    return Err(SqrtError::NegativeNumber(self.into()));
+                }
+                // softposit's integer path overflows on the largest posit16. The largest posit
+                // is an even power of two, so its root is exact in any format.
+                if self == $t::MAX {
+                    return Ok($t::from_f64(f64::from(self).sqrt()));
+                }
+                Ok(self.sqrt())
+            }
+        }
+    )*};
+}
+
+impl_sqrt_float_posit! {
+    P16E1: 1.049e-4, "1e-10 · √(2^40), as posit16 has 2^-12 spacing at one.";
+    P32E2: 5.793e-7, "1e-10 · √(2^25), as posit32 has 2^-27 spacing at one.";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_f32_kernel() -> Result<(), SqrtError> {
+        for number in [0.0_f32, 1e-6, 0.25, 2.0, 1e6, 3e30] {
+            let root = number.kernel_sqrt()?;
+            assert!(
+                (root - number.sqrt()).abs() <= number.sqrt() * 2.0 * f32::EPSILON,
+                "√{number} = {root}"
+            );
+        }
+        assert!(matches!(
+            (-1.0_f32).kernel_sqrt(),
+            Err(SqrtError::NegativeNumber(-1.0))
+        ));
+        assert!(f32::NAN.kernel_sqrt().is_err());
+        assert!(f32::INFINITY.kernel_sqrt().is_err());
+        Ok(())
+    }
+
+    #[cfg(feature = "posit")]
+    #[test]
+    fn test_posit_kernels() -> Result<(), SqrtError> {
+        // Every non-negative posit16, against the f64 root rounded once to posit16.
+        for bits in 0..=0x7fff_u16 {
+            let number = P16E1::from_bits(bits);
+            let expected = P16E1::from_f64(f64::from(number).sqrt());
+            assert_eq!(number.kernel_sqrt()?, expected, "√{number}");
+        }
+        for number in [0.25, 2.0, 1e-20, 1e30, f64::MAX] {
+            let root = P32E2::from_f64(number).kernel_sqrt()?;
+            assert_eq!(
+                root,
+                P32E2::from_f64(f64::from(P32E2::from_f64(number)).sqrt())
+            );
+        }
+        assert_eq!(P32E2::from_f64(16.0).kernel_sqrt()?, P32E2::from_f64(4.0));
+
+        assert!(matches!(
+            P16E1::from_f64(-4.0).kernel_sqrt(),
+            Err(SqrtError::NegativeNumber(-4.0))
+        ));
+        assert!(matches!(
+            P32E2::NAR.kernel_sqrt(),
+            Err(SqrtError::NonConvergence { .. })
+        ));
+        assert!(P16E1::NAR.is_nan() && P16E1::NAR.kernel_sqrt().is_err());
+        Ok(())
+    }
+
+    #[cfg(feature = "half")]
+    #[test]
+    fn test_half_kernels_round_correctly() -> Result<(), SqrtError> {
+        // Every non-negative finite value, compared with the exact root rounded from f64.
+        for bits in 0..0x7c00 {
+            let number = f16::from_bits(bits);
+            let expected = f16::from_f64(f64::from(number).sqrt());
+            assert_eq!(number.kernel_sqrt()?, expected, "√{number}");
+        }
+        for bits in 0..0x7f80 {
+            let number = bf16::from_bits(bits);
+            let expected = bf16::from_f64(f64::from(number).sqrt());
+            assert_eq!(number.kernel_sqrt()?, expected, "√{number}");
+        }
+        assert!(matches!(
+            f16::from_f32(-4.0).kernel_sqrt(),
+            Err(SqrtError::NegativeNumber(-4.0))
+        ));
+        assert!(bf16::NAN.kernel_sqrt().is_err());
+        assert!(f16::INFINITY.kernel_sqrt().is_err());
+        Ok(())
+    }
+}
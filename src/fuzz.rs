@@ -0,0 +1,178 @@
+//! Fuzzing entry points over the crate's input-parsing and processing surface.
+//!
+//! Each function takes arbitrary bytes, decodes them into the inputs of a public API and panics
+//! only if an invariant of that API is broken, so it can be dropped straight into a
+//! `cargo fuzz` target:
+//!
+//! ```ignore
+//! libfuzzer_sys::fuzz_target!(|data: &[u8]| sqrtx::fuzz::options_blob(data));
+//! ```
+//!
+//! The byte layouts are not stable and may change in any release.
+
+use crate::{
+    inverse_square_root, kernel, square_root_exact, square_roots_parallel_sync_with, BatchLimits,
+    BatchRequest, SqrtOptions,
+};
+
+/// Decodes `data` as an options blob. Whatever decodes must encode to a blob that decodes back to
+/// the same options.
+pub fn options_blob(data: &[u8]) {
+    let Ok(options) = SqrtOptions::from_versioned_bytes(data) else {
+        return;
+    };
+    // Compared as bytes, since a `NaN` tolerance or clamp bound never equals itself.
+    let encoded = options.to_versioned_bytes();
+    let decoded = SqrtOptions::from_versioned_bytes(&encoded).expect("re-encoded blob decodes");
+    assert_eq!(decoded.to_versioned_bytes(), encoded);
+}
+
+/// Runs a whole batch described by `data`: one byte of element limit, one byte of options blob
+/// length, the blob (default options if it does not decode) and then the inputs as
+/// little-endian `f64`s.
+pub fn batch_request(data: &[u8]) {
+    let [max_elements, blob_len, rest @ ..] = data else {
+        return;
+    };
+    let (blob, inputs) = rest.split_at(usize::from(*blob_len).min(rest.len()));
+    let options = SqrtOptions::from_versioned_bytes(blob).unwrap_or_default();
+    let limits = BatchLimits::new().max_elements(usize::from(*max_elements));
+
+    let Ok(request) = BatchRequest::try_new(f64s(inputs), limits) else {
+        assert!(inputs.len() / 8 > usize::from(*max_elements));
+        return;
+    };
+    let elements = request.len();
+    if let Ok((roots, report)) = square_roots_parallel_sync_with(request.into_vec(), &options) {
+        assert_eq!(roots.len(), elements);
+        assert_eq!(report.elements, elements);
+    }
+}
+
+/// Takes the roots of every little-endian `f64` in `data` through the scalar kernels, checking
+/// the correctly rounded path against [`f64::sqrt`], the cube root against [`f64::cbrt`] and the
+/// inverse square root against its definition.
+pub fn kernels(data: &[u8]) {
+    for number in f64s(data) {
+        let exact = square_root_exact(number);
+        if number.is_finite() && number >= 0.0 {
+            let exact = exact.expect("finite non-negative inputs have a root");
+            assert_eq!(exact.to_bits(), number.sqrt().to_bits(), "√{number}");
+
+            let inverse = inverse_square_root(number).expect("the inverse root converges");
+            let expected = 1.0 / number.sqrt();
+            assert!(
+                inverse == expected || (inverse - expected).abs() <= 4.0 * f64::EPSILON * expected,
+                "1/√{number} = {inverse}"
+            );
+        } else {
+            assert!(exact.is_err(), "√{number} is rejected");
+        }
+        if number.is_finite() {
+            let root = crate::cube_root(number).expect("finite inputs have a cube root");
+            let expected = number.cbrt();
+            assert!(
+                (root - expected).abs() <= 2.0 * f64::EPSILON * expected.abs(),
+                "∛{number} = {root}"
+            );
+        }
+        let _ = kernel::sqrt(number);
+        for degree in 0..=5 {
+            let _ = kernel::newton_nth_root(number, degree);
+        }
+    }
+}
+
+/// Computes a decimal root: the first byte is the number of digits, the rest the decimal text.
+///
+/// Exponents of more than four digits are skipped, since `1e999999999` asks for a billion-digit
+/// integer by design rather than by accident.
+#[cfg(feature = "highprec")]
+pub fn decimal(data: &[u8]) {
+    let Some((&digits, text)) = data.split_first() else {
+        return;
+    };
+    let Ok(text) = std::str::from_utf8(text) else {
+        return;
+    };
+    if let Some(at) = text.find(['e', 'E']) {
+        let exponent = text[at + 1..]
+            .trim_start_matches(['+', '-'])
+            .trim_start_matches('0');
+        if exponent.len() > 4 {
+            return;
+        }
+    }
+    if let Ok(root) = crate::square_root_precise(text, u32::from(digits)) {
+        let fraction = root
+            .split_once('.')
+            .map_or(0, |(_, fraction)| fraction.len());
+        assert_eq!(fraction, usize::from(digits), "√{text} to {digits} places");
+        assert!(root
+            .bytes()
+            .all(|byte| byte.is_ascii_digit() || byte == b'.'));
+    }
+}
+
+/// The little-endian `f64`s in `data`, ignoring a trailing partial one.
+fn f64s(data: &[u8]) -> impl Iterator<Item = f64> + '_ {
+    data.chunks_exact(8)
+        .map(|chunk| f64::from_le_bytes(chunk.try_into().expect("chunks are eight bytes")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Algorithm;
+
+    /// A few corpora of pseudo-random bytes, plus the empty input.
+    fn corpora() -> Vec<Vec<u8>> {
+        let mut bytes = crate::fixtures::random_bits(372).map(|bits| (bits >> 56) as u8);
+        let mut corpora = vec![Vec::new()];
+        for len in [1, 7, 64, 300] {
+            corpora.push(bytes.by_ref().take(len).collect());
+        }
+        corpora
+    }
+
+    #[test]
+    fn test_entry_points_accept_arbitrary_bytes() {
+        for data in corpora() {
+            options_blob(&data);
+            batch_request(&data);
+            kernels(&data);
+            #[cfg(feature = "highprec")]
+            decimal(&data);
+        }
+    }
+
+    #[test]
+    fn test_entry_points_on_valid_inputs() {
+        let blob = SqrtOptions::new()
+            .clamp(0.0, f64::NAN)
+            .algorithm(Algorithm::Bisection)
+            .to_versioned_bytes();
+        options_blob(&blob);
+
+        let mut data = vec![3, blob.len() as u8];
+        data.extend_from_slice(&blob);
+        for number in [4.0f64, 2.0, 1e300] {
+            data.extend_from_slice(&number.to_le_bytes());
+        }
+        batch_request(&data);
+        data[0] = 2;
+        batch_request(&data);
+
+        let numbers: Vec<u8> = [0.0f64, f64::from_bits(1), 2.0, -1.0, f64::NAN, f64::MAX]
+            .iter()
+            .flat_map(|number| number.to_le_bytes())
+            .collect();
+        kernels(&numbers);
+
+        #[cfg(feature = "highprec")]
+        {
+            decimal(b"\x052.25");
+            decimal(b"\x021e99999999999");
+        }
+    }
+}
@@ -0,0 +1,207 @@
+use crate::Result;
+
+use crate::{batch, BatchReport, ExecutionBackend, SqrtError, SqrtOptions};
+
+/// Counts of values falling into equal-width bins over `[low, high]`.
+///
+/// The last bin is closed, so `high` itself is counted in it. Values outside the range are
+/// counted in `underflow` and `overflow` rather than dropped.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    /// Lower edge of the first bin.
+    pub low: f64,
+    /// Upper edge of the last bin.
+    pub high: f64,
+    /// Number of values in each bin.
+    pub counts: Vec<usize>,
+    /// Number of values below `low`.
+    pub underflow: usize,
+    /// Number of values above `high`.
+    pub overflow: usize,
+}
+
+impl Histogram {
+    /// Creates an empty histogram of `bins` equal-width bins over `[low, high]`.
+    ///
+    /// # Returns
+    /// - `Ok(Histogram)` if the range is finite and non-empty and `bins` is at least 1.
+    /// - `Err(SqrtError::InvalidHistogram)` otherwise.
+    pub fn new(low: f64, high: f64, bins: usize) -> Result<Self, SqrtError> {
+        let valid = bins > 0 && low < high && (high - low).is_finite();
+        if !valid {
+            return Err(SqrtError::InvalidHistogram { low, high, bins });
+        }
+        Ok(Self {
+            low,
+            high,
+            counts: vec![0; bins],
+            underflow: 0,
+            overflow: 0,
+        })
+    }
+
+    /// Width of each bin.
+    pub fn bin_width(&self) -> f64 {
+        (self.high - self.low) / self.counts.len() as f64
+    }
+
+    /// The `[start, end)` edges of bin `index`.
+    pub fn bin_range(&self, index: usize) -> (f64, f64) {
+        let width = self.bin_width();
+        (
+            self.low + width * index as f64,
+            self.low + width * (index + 1) as f64,
+        )
+    }
+
+    /// Total number of values recorded, including those out of range.
+    pub fn total(&self) -> usize {
+        self.counts.iter().sum::<usize>() + self.underflow + self.overflow
+    }
+
+    /// Counts `value` in its bin.
+    pub fn record(&mut self, value: f64) {
+        if value < self.low {
+            self.underflow += 1;
+        } else if value > self.high {
+            self.overflow += 1;
+        } else {
+            let bins = self.counts.len();
+            let index = ((value - self.low) / self.bin_width()) as usize;
+            self.counts[index.min(bins - 1)] += 1;
+        }
+    }
+
+    /// Adds the counts of `other`, which must have the same range and number of bins.
+    fn merge(mut self, other: Self) -> Self {
+        for (count, other) in self.counts.iter_mut().zip(other.counts) {
+            *count += other;
+        }
+        self.underflow += other.underflow;
+        self.overflow += other.overflow;
+        self
+    }
+}
+
+/// Computes square roots in parallel and histograms them in the same pass.
+///
+/// Runs on rayon's global thread pool; use [`sqrt_histogram_with`] to choose the backend and
+/// kernel options.
+///
+/// # Arguments
+/// - `numbers`: The input numbers (all must be non-negative).
+/// - `bins`: An empty histogram defining the range and number of bins, from [`Histogram::new`].
+///
+/// # Returns
+/// - `Ok((Vec<f64>, Histogram))` with the roots in input order and their histogram.
+/// - `Err(anyhow::Error)` if any input number is negative or fails to converge.
+pub fn sqrt_histogram(numbers: &[f64], bins: &Histogram) -> Result<(Vec<f64>, Histogram)> {
+    let options = SqrtOptions::new().backend(ExecutionBackend::Rayon);
+    let (roots, histogram, _) = sqrt_histogram_with(numbers, bins, &options)?;
+    Ok((roots, histogram))
+}
+
+/// Computes square roots on the configured backend, applying the given options, and histograms
+/// them in the same pass.
+///
+/// Each chunk's roots are counted as soon as it completes, after flushing and clamping.
+///
+/// # Arguments
+/// - `numbers`: The input numbers (all must be non-negative).
+/// - `bins`: An empty histogram defining the range and number of bins, from [`Histogram::new`].
+/// - `options`: The options controlling the computation.
+///
+/// # Returns
+/// - `Ok((Vec<f64>, Histogram, BatchReport))` with the roots in input order, their histogram
+///   and a summary of the batch.
+/// - `Err(anyhow::Error)` if any input number is negative or fails to converge, or the deadline
+///   passes; the `partial` roots of [`SqrtError::DeadlineExceeded`] are then empty.
+pub fn sqrt_histogram_with(
+    numbers: &[f64],
+    bins: &Histogram,
+    options: &SqrtOptions,
+) -> Result<(Vec<f64>, Histogram, BatchReport)> {
+    let empty = Histogram {
+        counts: vec![0; bins.counts.len()],
+        underflow: 0,
+        overflow: 0,
+        ..*bins
+    };
+
+    let (chunks, report) = batch::run_batch_folded(numbers, options, |_, roots| {
+        let mut histogram = empty.clone();
+        for &root in &roots {
+            histogram.record(root);
+        }
+        (roots, histogram)
+    })?;
+
+    let mut roots = Vec::with_capacity(numbers.len());
+    let mut histogram = empty;
+    for (chunk, chunk_histogram) in chunks {
+        roots.extend(chunk);
+        histogram = histogram.merge(chunk_histogram);
+    }
+    Ok((roots, histogram, report))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sqrt_histogram() -> Result<()> {
+        // Roots cycle through 0.5, 1.5, ..., 11.5, away from every bin edge.
+        let numbers: Vec<f64> = (0..20_000)
+            .map(|i| (f64::from(i % 12) + 0.5).powi(2))
+            .collect();
+        let bins = Histogram::new(1.0, 10.0, 9)?;
+        let (roots, histogram) = sqrt_histogram(&numbers, &bins)?;
+
+        assert_eq!(roots.len(), numbers.len());
+        assert!((roots[13] - 1.5).abs() < 1e-10);
+        assert_eq!(histogram.total(), numbers.len());
+        assert_eq!(histogram.underflow, 1667);
+        assert_eq!(histogram.overflow, 2 * 1666);
+        assert_eq!(histogram.counts[0], 1667);
+        assert_eq!(histogram.counts[8], 1666);
+        assert_eq!(histogram.bin_range(2), (3.0, 4.0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_sqrt_histogram_with_options() -> Result<()> {
+        let numbers: Vec<f64> = (0..3 * batch::CHUNK_LEN).map(|i| i as f64).collect();
+        let bins = Histogram::new(0.0, 100.0, 10)?;
+        let expected = sqrt_histogram(&numbers, &bins)?;
+        for backend in [ExecutionBackend::Sequential, ExecutionBackend::Rayon] {
+            let options = SqrtOptions::new().backend(backend);
+            let (roots, histogram, report) = sqrt_histogram_with(&numbers, &bins, &options)?;
+            assert_eq!((roots, histogram), expected);
+            assert_eq!(report.elements, numbers.len());
+        }
+
+        // Clamped roots are counted where they were clamped to.
+        let options = SqrtOptions::new().clamp(0.0, 50.0);
+        let (_, histogram, report) = sqrt_histogram_with(&numbers, &bins, &options)?;
+        assert_eq!(histogram.overflow, 0);
+        assert_eq!(histogram.counts[5], numbers.len() - 50 * 50);
+        assert_eq!(report.clamped, numbers.len() - 50 * 50 - 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_histogram() {
+        assert!(Histogram::new(0.0, 1.0, 0).is_err());
+        assert!(Histogram::new(1.0, 1.0, 4).is_err());
+        assert!(Histogram::new(0.0, f64::INFINITY, 4).is_err());
+        assert!(Histogram::new(f64::NAN, 1.0, 4).is_err());
+    }
+
+    #[test]
+    fn test_sqrt_histogram_negative() -> Result<()> {
+        let bins = Histogram::new(0.0, 1.0, 2)?;
+        assert!(sqrt_histogram(&[1.0, -1.0], &bins).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+//! Hardware square root instructions for AArch64 (`FSQRT`) and RISC-V with the D extension
+//! (`fsqrt.d`).
+//!
+//! On every other target [`sqrt`] falls back to [`f64::sqrt`], which compiles to the target's own
+//! instruction where it has one (`sqrtsd` on x86_64), or to the integer-only software path in
+//! `soft-float` builds. The tests verify every path against the software one.
+
+/// Whether [`sqrt`] uses a dedicated instruction on this target.
+#[cfg_attr(not(test), allow(dead_code))]
+pub(crate) const HAS_INSTRUCTION: bool = cfg!(any(
+    target_arch = "aarch64",
+    all(
+        any(target_arch = "riscv32", target_arch = "riscv64"),
+        target_feature = "d"
+    ),
+));
+
+/// Correctly rounded IEEE-754 square root using `FSQRT`.
+#[cfg(target_arch = "aarch64")]
+#[inline]
+pub(crate) fn sqrt(x: f64) -> f64 {
+    let root: f64;
+    // SAFETY: `fsqrt` only reads and writes the given FP registers.
+    unsafe {
+        core::arch::asm!(
+            "fsqrt {root:d}, {x:d}",
+            x = in(vreg) x,
+            root = lateout(vreg) root,
+            options(pure, nomem, nostack),
+        );
+    }
+    root
+}
+
+/// Correctly rounded IEEE-754 square root using `fsqrt.d`.
+#[cfg(all(
+    any(target_arch = "riscv32", target_arch = "riscv64"),
+    target_feature = "d"
+))]
+#[inline]
+pub(crate) fn sqrt(x: f64) -> f64 {
+    let root: f64;
+    // SAFETY: `fsqrt.d` only reads and writes the given FP registers.
+    unsafe {
+        core::arch::asm!(
+            "fsqrt.d {root}, {x}",
+            x = in(freg) x,
+            root = lateout(freg) root,
+            options(pure, nomem, nostack),
+        );
+    }
+    root
+}
+
+/// Correctly rounded IEEE-754 square root from the standard library.
+#[cfg(not(any(
+    feature = "soft-float",
+    target_arch = "aarch64",
+    all(
+        any(target_arch = "riscv32", target_arch = "riscv64"),
+        target_feature = "d"
+    ),
+)))]
+#[inline]
+pub(crate) fn sqrt(x: f64) -> f64 {
+    x.sqrt()
+}
+
+/// Correctly rounded IEEE-754 square root computed in software.
+#[cfg(all(
+    feature = "soft-float",
+    not(any(
+        target_arch = "aarch64",
+        all(
+            any(target_arch = "riscv32", target_arch = "riscv64"),
+            target_feature = "d"
+        ),
+    ))
+))]
+#[inline]
+pub(crate) fn sqrt(x: f64) -> f64 {
+    f64::from_bits(crate::soft::sqrt_bits(x.to_bits()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_software_path() {
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        for _ in 0..10_000 {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let x = f64::from_bits(state >> 1);
+            let expected = crate::soft::sqrt_bits(x.to_bits());
+            if x.is_nan() {
+                assert!(sqrt(x).is_nan());
+            } else {
+                assert_eq!(sqrt(x).to_bits(), expected, "sqrt({x:e})");
+            }
+        }
+    }
+
+    #[test]
+    fn test_instruction_detection() {
+        assert_eq!(
+            HAS_INSTRUCTION,
+            cfg!(target_arch = "aarch64") || cfg!(target_feature = "d")
+        );
+    }
+}
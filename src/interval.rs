@@ -0,0 +1,205 @@
+use std::cmp::Ordering;
+
+use crate::{Result, SqrtError};
+
+/// A closed interval `[lo, hi]` of reals with `f64` endpoints.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Interval {
+    /// Lower endpoint.
+    pub lo: f64,
+    /// Upper endpoint.
+    pub hi: f64,
+}
+
+impl Interval {
+    /// Creates the interval `[lo, hi]`.
+    ///
+    /// # Returns
+    /// - `Ok(Interval)` if `lo <= hi`. Infinite endpoints are allowed.
+    /// - `Err(SqrtError::InvalidInterval)` if `lo > hi` or either endpoint is `NaN`.
+    pub fn new(lo: f64, hi: f64) -> Result<Self, SqrtError> {
+        if lo <= hi {
+            Ok(Self { lo, hi })
+        } else {
+            Err(SqrtError::InvalidInterval { lo, hi })
+        }
+    }
+
+    /// Whether `value` lies in the interval.
+    pub fn contains(&self, value: f64) -> bool {
+        self.lo <= value && value <= self.hi
+    }
+
+    /// Encloses the square roots of every value in the interval.
+    ///
+    /// Both endpoints are rounded outward: `lo` of the result is the largest `f64` whose square
+    /// is at most `self.lo`, and `hi` the smallest whose square is at least `self.hi`.
+    /// The squares are compared exactly on integers, so the enclosure holds whatever the
+    /// floating-point rounding mode or square root kernel.
+    ///
+    /// # Returns
+    /// - `Ok(Interval)` containing `√x` for every `x` in `self`.
+    /// - `Err(SqrtError::NegativeNumber)` if the interval contains a negative number.
+    pub fn sqrt(&self) -> Result<Self, SqrtError> {
+        if self.lo < 0.0 {
+            return Err(SqrtError::NegativeNumber(self.lo));
+        }
+        Ok(Self {
+            lo: sqrt_down(self.lo),
+            hi: sqrt_up(self.hi),
+        })
+    }
+}
+
+/// Splits a non-negative finite `value` into `mantissa · 2^exponent`.
+fn decompose(value: f64) -> (u128, i32) {
+    let bits = value.to_bits();
+    let exponent = (bits >> 52) as i32;
+    let fraction = u128::from(bits & ((1 << 52) - 1));
+    if exponent == 0 {
+        (fraction, -1074)
+    } else {
+        (fraction | 1 << 52, exponent - 1075)
+    }
+}
+
+/// Compares `root²` with `value` exactly, for non-negative finite arguments.
+fn cmp_square(root: f64, value: f64) -> Ordering {
+    if root == 0.0 || value == 0.0 {
+        return (root > 0.0).cmp(&(value > 0.0));
+    }
+    let (mantissa, exponent) = decompose(root);
+    let (square, square_exponent) = (mantissa * mantissa, 2 * exponent);
+    let (target, target_exponent) = decompose(value);
+
+    // Compare magnitudes first, then align the mantissas; the shift is then at most 106 bits and
+    // never overflows.
+    let bits = |m: u128| 128 - m.leading_zeros() as i32;
+    let square_top = bits(square) + square_exponent;
+    let target_top = bits(target) + target_exponent;
+    if square_top != target_top {
+        return square_top.cmp(&target_top);
+    }
+    let shift = bits(target) - bits(square);
+    if shift >= 0 {
+        (square << shift).cmp(&target)
+    } else {
+        square.cmp(&(target << -shift))
+    }
+}
+
+/// The largest `f64` whose square is at most `value`, found by bisecting the bit patterns of
+/// non-negative floats, which are ordered like the floats themselves.
+fn sqrt_down(value: f64) -> f64 {
+    if value == f64::INFINITY {
+        return f64::MAX;
+    }
+    let (mut below, mut above) = (0u64, f64::MAX.to_bits());
+    while above - below > 1 {
+        let middle = below + (above - below) / 2;
+        if cmp_square(f64::from_bits(middle), value) == Ordering::Greater {
+            above = middle;
+        } else {
+            below = middle;
+        }
+    }
+    f64::from_bits(below)
+}
+
+/// The smallest `f64` whose square is at least `value`.
+fn sqrt_up(value: f64) -> f64 {
+    if value == f64::INFINITY {
+        return f64::INFINITY;
+    }
+    let root = sqrt_down(value);
+    if cmp_square(root, value) == Ordering::Less {
+        root.next_up()
+    } else {
+        root
+    }
+}
+
+/// Computes a rigorous enclosure of the square roots of `[lo, hi]`.
+///
+/// # Arguments
+/// - `lo`: The lower endpoint (must be non-negative).
+/// - `hi`: The upper endpoint, at least `lo`. May be `+∞`.
+///
+/// # Returns
+/// - `Ok((f64, f64))` with endpoints rounded outward, so that `√x` lies within them for every
+///   `x` in `[lo, hi]`. See [`Interval::sqrt`].
+/// - `Err(anyhow::Error)` if the interval is invalid or contains a negative number.
+///
+/// ```
+/// let (lo, hi) = sqrtx::square_root_interval(2.0, 2.0)?;
+/// assert_eq!((lo, hi), (2f64.sqrt().next_down(), 2f64.sqrt()));
+/// # Ok::<(), sqrtx::Error>(())
+/// ```
+pub fn square_root_interval(lo: f64, hi: f64) -> Result<(f64, f64)> {
+    let root = Interval::new(lo, hi)?.sqrt()?;
+    Ok((root.lo, root.hi))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cmp_square() {
+        assert_eq!(cmp_square(3.0, 9.0), Ordering::Equal);
+        assert_eq!(cmp_square(3.0, 9.0f64.next_up()), Ordering::Less);
+        assert_eq!(cmp_square(3.0f64.next_up(), 9.0), Ordering::Greater);
+        assert_eq!(cmp_square(0.0, f64::from_bits(1)), Ordering::Less);
+        assert_eq!(cmp_square(f64::MAX, f64::MAX), Ordering::Greater);
+        assert_eq!(
+            cmp_square(2f64.powi(-537), f64::from_bits(1)),
+            Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn test_square_root_interval_encloses() -> Result<()> {
+        let values = [
+            f64::from_bits(3),
+            f64::MIN_POSITIVE * 1.5,
+            1e-300,
+            0.5,
+            2.0,
+            3.0,
+            1e300,
+            f64::MAX,
+        ];
+        for value in values {
+            let (lo, hi) = square_root_interval(value, value)?;
+            assert_eq!(cmp_square(lo, value), Ordering::Less, "√{value}");
+            assert_eq!(cmp_square(hi, value), Ordering::Greater, "√{value}");
+            assert_eq!(lo.next_up(), hi, "√{value} is one ulp wide");
+        }
+
+        assert_eq!(square_root_interval(4.0, 9.0)?, (2.0, 3.0));
+        let tiny = 2f64.powi(-537);
+        assert_eq!(square_root_interval(f64::from_bits(1), 1.0)?, (tiny, 1.0));
+        assert_eq!(
+            square_root_interval(0.0, f64::INFINITY)?,
+            (0.0, f64::INFINITY)
+        );
+
+        let interval = Interval::new(2.0, 8.0)?.sqrt()?;
+        assert!(interval.contains(2f64.sqrt()) && interval.contains(8f64.sqrt()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_square_root_interval_errors() {
+        assert!(matches!(
+            Interval::new(2.0, 1.0),
+            Err(SqrtError::InvalidInterval { .. })
+        ));
+        assert!(Interval::new(f64::NAN, 1.0).is_err());
+        assert!(matches!(
+            Interval::new(-1.0, 4.0).and_then(|interval| interval.sqrt()),
+            Err(SqrtError::NegativeNumber(-1.0))
+        ));
+        assert!(square_root_interval(-4.0, -1.0).is_err());
+    }
+}
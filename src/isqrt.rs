@@ -0,0 +1,235 @@
+#[cfg(feature = "bigint")]
+use num_bigint::BigUint;
+
+use crate::kernel;
+
+/// Exact integer square roots with a choice of rounding.
+///
+/// All methods are computed on integers only, so they are exact for every value of the type,
+/// including those too large to round-trip through `f64`.
+///
+/// ```
+/// use sqrtx::ISqrt;
+///
+/// assert_eq!(ISqrt::isqrt(10u64), 3);
+/// assert_eq!(10u64.isqrt_ceil(), 4);
+/// assert_eq!(10u64.isqrt_round(), 3);
+/// ```
+pub trait ISqrt: Sized {
+    /// Returns `⌊√self⌋`.
+    fn isqrt(self) -> Self;
+
+    /// Returns `⌈√self⌉`, the smallest `r` with `r * r >= self`.
+    fn isqrt_ceil(self) -> Self;
+
+    /// Returns `√self` rounded to the nearest integer. Ties cannot occur for integer inputs.
+    fn isqrt_round(self) -> Self;
+}
+
+macro_rules! impl_isqrt {
+    ($($t:ty),*) => {$(
+        impl ISqrt for $t {
+            fn isqrt(self) -> Self {
+                <$t>::isqrt(self)
+            }
+
+            fn isqrt_ceil(self) -> Self {
+                let root = <$t>::isqrt(self);
+                if root * root == self {
+                    root
+                } else {
+                    root + 1
+                }
+            }
+
+            fn isqrt_round(self) -> Self {
+                // √n ≥ r + ½ exactly when n ≥ r² + r + ¼, i.e. n > r² + r for integers.
+                let root = <$t>::isqrt(self);
+                if self - root * root > root {
+                    root + 1
+                } else {
+                    root
+                }
+            }
+        }
+    )*};
+}
+
+impl_isqrt!(u8, u16, u32, u64, u128, usize);
+
+/// Returns `⌊√n⌋` exactly for any `u64`, without going through floating point.
+///
+/// ```
+/// // (2³² − 1)² − 1 rounds up to a perfect square in f64.
+/// assert_eq!(sqrtx::isqrt_u64(18_446_744_065_119_617_024), 4_294_967_294);
+/// ```
+pub const fn isqrt_u64(n: u64) -> u64 {
+    n.isqrt()
+}
+
+/// Returns `⌊√n⌋` exactly for any `u128`, without going through floating point.
+pub const fn isqrt_u128(n: u128) -> u128 {
+    n.isqrt()
+}
+
+/// Returns `(⌊√n⌋, n − ⌊√n⌋²)`, like GMP's `mpz_sqrtrem`.
+///
+/// The remainder is zero exactly when `n` is a perfect square, and at most `2·⌊√n⌋` otherwise.
+///
+/// ```
+/// assert_eq!(sqrtx::isqrt_rem(50), (7, 1));
+/// assert_eq!(sqrtx::isqrt_rem(49), (7, 0));
+/// ```
+pub const fn isqrt_rem(n: u64) -> (u64, u64) {
+    let root = n.isqrt();
+    (root, n - root * root)
+}
+
+/// Returns the exact square root of `n` if `n` is a perfect square.
+///
+/// ```
+/// assert_eq!(sqrtx::perfect_square_root(144), Some(12));
+/// assert_eq!(sqrtx::perfect_square_root(145), None);
+/// ```
+pub const fn perfect_square_root(n: u64) -> Option<u64> {
+    // Squares are 0, 1, 4 or 9 mod 16, which rejects three quarters of inputs cheaply.
+    if (0x0213u16 >> (n % 16)) & 1 == 0 {
+        return None;
+    }
+    let root = n.isqrt();
+    if root * root == n {
+        Some(root)
+    } else {
+        None
+    }
+}
+
+/// Whether `n` is the square of an integer, decided exactly.
+pub const fn is_perfect_square(n: u64) -> bool {
+    perfect_square_root(n).is_some()
+}
+
+/// Returns `⌊√n⌋` exactly for an unsigned integer of any size, e.g. a cryptographic modulus.
+///
+/// ```
+/// use num_bigint::BigUint;
+///
+/// let root = BigUint::from(10u32).pow(100) + 1u32;
+/// assert_eq!(sqrtx::isqrt_big(&(&root * &root - 1u32)), root - 1u32);
+/// ```
+#[cfg(feature = "bigint")]
+pub fn isqrt_big(n: &BigUint) -> BigUint {
+    n.sqrt()
+}
+
+/// Computes the square root of a length, e.g. to size sqrt-decomposition buckets or grids.
+///
+/// # Arguments
+/// - `len`: A collection length.
+///
+/// # Returns
+/// - `(usize, f64)` with the exact floor root and the floating-point root.
+///
+/// ```
+/// let (side, exact) = sqrtx::sqrt_of_len(vec![0; 10].len());
+/// assert_eq!(side, 3);
+/// assert!((exact - 10f64.sqrt()).abs() < 1e-10);
+/// ```
+pub fn sqrt_of_len(len: usize) -> (usize, f64) {
+    // Lengths are finite and non-negative, so the kernel cannot fail.
+    let root = kernel::sqrt(len as f64).unwrap_or(f64::NAN);
+    (ISqrt::isqrt(len), root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_isqrt_rounding_modes() {
+        for (n, floor, ceil, round) in [
+            (0u64, 0, 0, 0),
+            (1, 1, 1, 1),
+            (15, 3, 4, 4),
+            (16, 4, 4, 4),
+            (17, 4, 5, 4),
+            (20, 4, 5, 4),
+            (21, 4, 5, 5),
+        ] {
+            assert_eq!(ISqrt::isqrt(n), floor, "floor({n})");
+            assert_eq!(n.isqrt_ceil(), ceil, "ceil({n})");
+            assert_eq!(n.isqrt_round(), round, "round({n})");
+        }
+    }
+
+    #[test]
+    fn test_isqrt_extremes() {
+        assert_eq!(u8::MAX.isqrt_ceil(), 16);
+        assert_eq!(u8::MAX.isqrt_round(), 16);
+        assert_eq!(u64::MAX.isqrt_ceil(), 1 << 32);
+        assert_eq!(u128::MAX.isqrt_round(), 1 << 64);
+        assert_eq!(ISqrt::isqrt(u128::MAX), u64::MAX as u128);
+    }
+
+    #[test]
+    fn test_isqrt_free_functions_near_squares() {
+        for root in [1u64, 3, 94_906_265, 3_037_000_499, u32::MAX as u64] {
+            assert_eq!(isqrt_u64(root * root), root);
+            assert_eq!(isqrt_u64(root * root - 1), root - 1);
+            let wide = u128::from(root) << 32 | 7;
+            assert_eq!(isqrt_u128(wide * wide), wide);
+            assert_eq!(isqrt_u128(wide * wide - 1), wide - 1);
+        }
+        assert_eq!(isqrt_u64(u64::MAX), u32::MAX as u64);
+        assert_eq!(isqrt_u128(u128::MAX), u64::MAX as u128);
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn test_isqrt_big() {
+        assert_eq!(isqrt_big(&BigUint::ZERO), BigUint::ZERO);
+        assert_eq!(
+            isqrt_big(&BigUint::from(u128::MAX)),
+            BigUint::from(u64::MAX)
+        );
+
+        // The 601-digit square of a 300-digit root, and its neighbours.
+        let root = BigUint::from(7u32).pow(355);
+        let square = &root * &root;
+        assert_eq!(isqrt_big(&square), root);
+        assert_eq!(isqrt_big(&(&square - 1u32)), &root - 1u32);
+        assert_eq!(isqrt_big(&(&square + &root * 2u32)), root);
+    }
+
+    #[test]
+    fn test_perfect_squares() {
+        let squares: Vec<u64> = (0..1_000u64).filter(|&n| is_perfect_square(n)).collect();
+        assert_eq!(squares, (0..32).map(|k| k * k).collect::<Vec<_>>());
+
+        let largest = u64::from(u32::MAX);
+        assert_eq!(perfect_square_root(largest * largest), Some(largest));
+        // Both round to the same f64 as the square.
+        assert!(!is_perfect_square(largest * largest - 1));
+        assert!(!is_perfect_square(largest * largest + 1));
+        assert!(!is_perfect_square(u64::MAX));
+    }
+
+    #[test]
+    fn test_isqrt_rem() {
+        for n in 0..10_000u64 {
+            let (root, rem) = isqrt_rem(n);
+            assert_eq!(root * root + rem, n);
+            assert!(rem <= 2 * root);
+            assert_eq!(rem == 0, is_perfect_square(n));
+        }
+        assert_eq!(isqrt_rem(u64::MAX), (u32::MAX as u64, 2 * u32::MAX as u64));
+    }
+
+    #[test]
+    fn test_sqrt_of_len() {
+        assert_eq!(sqrt_of_len(0), (0, 0.0));
+        let (floor, root) = sqrt_of_len(usize::MAX);
+        assert_eq!(floor, ISqrt::isqrt(usize::MAX));
+        assert!((root - (usize::MAX as f64).sqrt()).abs() / root < 1e-15);
+    }
+}
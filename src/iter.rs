@@ -0,0 +1,148 @@
+use std::iter::FusedIterator;
+
+use crate::{kernel, Result, SqrtError};
+
+/// Lazy iterator of square roots, returned by [`sqrt_iter`](crate::sqrt_iter).
+///
+/// It is double-ended, exact-size and fused whenever the source iterator is, so `rev()`,
+/// `len()` and friends work as they would on the inputs.
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct SqrtIter<I> {
+    numbers: I,
+}
+
+impl<I> SqrtIter<I> {
+    pub(crate) fn new(numbers: I) -> Self {
+        Self { numbers }
+    }
+}
+
+impl<I: Iterator<Item = f64>> Iterator for SqrtIter<I> {
+    type Item = Result<f64, SqrtError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.numbers.next().map(kernel::sqrt)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.numbers.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.numbers.nth(n).map(kernel::sqrt)
+    }
+}
+
+impl<I: DoubleEndedIterator<Item = f64>> DoubleEndedIterator for SqrtIter<I> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.numbers.next_back().map(kernel::sqrt)
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.numbers.nth_back(n).map(kernel::sqrt)
+    }
+}
+
+impl<I: ExactSizeIterator<Item = f64>> ExactSizeIterator for SqrtIter<I> {
+    fn len(&self) -> usize {
+        self.numbers.len()
+    }
+}
+
+impl<I: FusedIterator<Item = f64>> FusedIterator for SqrtIter<I> {}
+
+/// Lazy iterator of square roots computed a chunk at a time, returned by
+/// [`sqrt_chunks`](crate::sqrt_chunks).
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct SqrtChunks<I> {
+    numbers: I,
+    chunk_size: usize,
+}
+
+impl<I> SqrtChunks<I> {
+    /// A `chunk_size` of zero is treated as one.
+    pub(crate) fn new(numbers: I, chunk_size: usize) -> Self {
+        Self {
+            numbers,
+            chunk_size: chunk_size.max(1),
+        }
+    }
+}
+
+impl<I: Iterator<Item = f64>> Iterator for SqrtChunks<I> {
+    type Item = Result<Vec<f64>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut roots = Vec::with_capacity(self.chunk_size.min(self.numbers.size_hint().0));
+        let mut failure = None;
+        for number in self.numbers.by_ref().take(self.chunk_size) {
+            match kernel::sqrt(number) {
+                Ok(root) => roots.push(root),
+                // Keep consuming the chunk so the next one starts on a chunk boundary.
+                Err(error) => failure = failure.or(Some(error)),
+            }
+        }
+        match failure {
+            Some(error) => Some(Err(error.into())),
+            None if roots.is_empty() => None,
+            None => Some(Ok(roots)),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.numbers.size_hint();
+        (
+            lower.div_ceil(self.chunk_size),
+            upper.map(|upper| upper.div_ceil(self.chunk_size)),
+        )
+    }
+}
+
+impl<I: FusedIterator<Item = f64>> FusedIterator for SqrtChunks<I> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sqrt_iter_preserves_source_traits() -> Result<(), SqrtError> {
+        let mut roots = SqrtIter::new(vec![1.0, 4.0, 9.0, 16.0].into_iter());
+        assert_eq!(roots.len(), 4);
+        assert!((roots.next_back().unwrap()? - 4.0).abs() < 1e-10);
+        assert!((roots.next().unwrap()? - 1.0).abs() < 1e-10);
+        assert_eq!(roots.len(), 2);
+
+        let reversed = SqrtIter::new([0.0, 4.0, 9.0].into_iter())
+            .rev()
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(reversed.len(), 3);
+        assert!((reversed[0] - 3.0).abs() < 1e-10);
+        assert!((reversed[2] - 0.0).abs() < 1e-10);
+
+        // Skipped inputs are not computed, so the negative one never errors.
+        let mut roots = SqrtIter::new([-1.0, -2.0, 25.0].into_iter());
+        assert!((roots.nth(2).unwrap()? - 5.0).abs() < 1e-10);
+        assert!(roots.next().is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_sqrt_chunks() -> crate::Result<()> {
+        let mut chunks = SqrtChunks::new((0..7).map(|i| f64::from(i * i)), 3);
+        assert_eq!(chunks.size_hint(), (3, Some(3)));
+        assert_eq!(chunks.next().transpose()?, Some(vec![0.0, 1.0, 2.0]));
+        assert_eq!(chunks.next().transpose()?.map(|chunk| chunk.len()), Some(3));
+        assert_eq!(chunks.next().transpose()?, Some(vec![6.0]));
+        assert!(chunks.next().is_none());
+
+        // A failing chunk is reported whole and iteration resumes at the next chunk.
+        let roots: Vec<_> = SqrtChunks::new([4.0, -1.0, 9.0, 16.0].into_iter(), 2).collect();
+        assert!(roots[0].is_err());
+        assert_eq!(roots[1].as_ref().ok(), Some(&vec![3.0, 4.0]));
+        assert_eq!(SqrtChunks::new([1.0].into_iter(), 0).count(), 1);
+        assert_eq!(SqrtChunks::new([1.0].into_iter(), usize::MAX).count(), 1);
+        Ok(())
+    }
+}
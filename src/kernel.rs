@@ -0,0 +1,912 @@
+use crate::{Algorithm, SqrtError, SqrtFloat, SqrtOptions};
+
+/// The algorithm an `only-*` feature fixes at compile time, if any, ignoring
+/// [`SqrtOptions::algorithm`]. Being a constant, every other arm of the dispatch in [`sqrt`] and
+/// [`sqrt_with`] folds away and the unused algorithms are never linked in.
+///
+/// `only-hardware` takes precedence over `only-halley`, which takes precedence over
+/// `only-bisection` and then `only-newton`.
+pub(crate) const ONLY_ALGORITHM: Option<Algorithm> = if cfg!(feature = "only-hardware") {
+    Some(Algorithm::Hardware)
+} else if cfg!(feature = "only-halley") {
+    Some(Algorithm::Halley)
+} else if cfg!(feature = "only-bisection") {
+    Some(Algorithm::Bisection)
+} else if cfg!(feature = "only-newton") {
+    Some(Algorithm::Newton)
+} else {
+    None
+};
+
+/// Square root kernel used by every public entry point.
+///
+/// This is the Newton–Raphson iteration unless a feature selects another implementation:
+/// `soft-float` for the integer-only software path, or `hardware` for the target's square root
+/// instruction (see [`crate::hw`]). Both are correctly rounded and reject negative, `NaN` and infinite inputs
+/// with the same errors as the Newton–Raphson path. `soft-float` takes precedence, and an
+/// `only-*` feature takes precedence over both.
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub(crate) fn sqrt(number: f64) -> Result<f64, SqrtError> {
+    if let Some(algorithm) = ONLY_ALGORITHM {
+        return run_algorithm(algorithm, number, default_tolerance(algorithm), 0.0, None);
+    }
+
+    #[cfg(feature = "soft-float")]
+    return exact_sqrt(number);
+
+    #[cfg(all(feature = "hardware", not(feature = "soft-float")))]
+    return hardware_sqrt(number);
+
+    #[cfg(not(any(feature = "soft-float", feature = "hardware")))]
+    newton_sqrt(number)
+}
+
+/// [`sqrt`] accurate to a few ulps relative to the root at every magnitude, for callers whose
+/// inputs are routinely far from one, such as variances or eigenvalues in small units.
+///
+/// The correctly rounded kernels already are; the Newton–Raphson path stops on a relative step
+/// here instead of the default absolute one.
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub(crate) fn relative_sqrt(number: f64) -> Result<f64, SqrtError> {
+    #[cfg(any(feature = "soft-float", feature = "hardware"))]
+    return sqrt(number);
+
+    #[cfg(not(any(feature = "soft-float", feature = "hardware")))]
+    newton_sqrt_with(number, 0.0, 4.0 * f64::EPSILON, None)
+}
+
+/// [`sqrt`] with the algorithm and convergence settings of `options`.
+///
+/// Without an explicit [`Algorithm`] this is the crate kernel. The tolerances and iteration cap
+/// only exist for the iterative algorithms; the `soft-float` and `hardware` kernels are correctly
+/// rounded at a fixed cost and ignore them.
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub(crate) fn sqrt_with(number: f64, options: &SqrtOptions) -> Result<f64, SqrtError> {
+    let algorithm = ONLY_ALGORITHM.or(options.algorithm);
+    // A relative tolerance replaces the default absolute one rather than adding to it.
+    let tolerance = match (options.tolerance, options.relative_tolerance) {
+        (Some(tolerance), _) => tolerance,
+        (None, Some(_)) => 0.0,
+        (None, None) => algorithm.map_or(f64::TOLERANCE, default_tolerance),
+    };
+    let relative = options.relative_tolerance.unwrap_or(0.0);
+    let cap = options.max_iterations;
+    match algorithm {
+        Some(algorithm) => run_algorithm(algorithm, number, tolerance, relative, cap),
+        #[cfg(any(feature = "soft-float", feature = "hardware"))]
+        None => sqrt(number),
+        #[cfg(not(any(feature = "soft-float", feature = "hardware")))]
+        None => newton_sqrt_with(number, tolerance, relative, cap),
+    }
+}
+
+/// Absolute tolerance of `algorithm` when none is configured: [`SqrtFloat::TOLERANCE`], except
+/// that bisection, which would stop up to `1e-10` away from the root, narrows its bracket as far
+/// as it goes.
+fn default_tolerance(algorithm: Algorithm) -> f64 {
+    match algorithm {
+        Algorithm::Bisection => 0.0,
+        Algorithm::Newton | Algorithm::Halley | Algorithm::Hardware => f64::TOLERANCE,
+    }
+}
+
+/// Runs the `f64` root of `algorithm` with the given stopping rules.
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+fn run_algorithm(
+    algorithm: Algorithm,
+    number: f64,
+    tolerance: f64,
+    relative: f64,
+    cap: Option<u32>,
+) -> Result<f64, SqrtError> {
+    match algorithm {
+        Algorithm::Newton => newton_sqrt_with(number, tolerance, relative, cap),
+        Algorithm::Halley => halley_sqrt(number, tolerance, relative, cap),
+        Algorithm::Bisection => bisection_sqrt(number, tolerance, relative, cap),
+        Algorithm::Hardware => hardware_sqrt(number),
+    }
+}
+
+/// Correctly rounded square root, computed on the bit pattern by the integer-only software path
+/// whatever kernel the features select.
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub(crate) fn exact_sqrt(number: f64) -> Result<f64, SqrtError> {
+    check_ieee_input(number).map(|()| f64::from_bits(crate::soft::sqrt_bits(number.to_bits())))
+}
+
+/// Correctly rounded square root using the target's instruction, see [`crate::hw`].
+fn hardware_sqrt(number: f64) -> Result<f64, SqrtError> {
+    check_ieee_input(number).map(|()| crate::hw::sqrt(number))
+}
+
+/// Rejects the inputs the Newton–Raphson path errors on, inspecting the bit pattern only.
+fn check_ieee_input(number: f64) -> Result<(), SqrtError> {
+    let bits = number.to_bits();
+    let magnitude = bits & !(1 << 63);
+    if magnitude >= 0x7ff << 52 {
+        return Err(SqrtError::NonConvergence {
+            iterations: 1,
+            residual: f64::NAN,
+        });
+    }
+    if bits >> 63 == 1 && magnitude != 0 {
+        return Err(SqrtError::NegativeNumber(number));
+    }
+    Ok(())
+}
+
+/// Newton–Raphson square root, generic over the float type.
+///
+/// The iteration starts from [`SqrtFloat::initial_guess`] and stops once two successive guesses
+/// differ by less than [`SqrtFloat::TOLERANCE`] (`1e-10` for `f64`). If the step between
+/// guesses stops shrinking (the iteration is oscillating or has stalled, e.g. on `NaN` or
+/// infinite input) the kernel gives up with [`SqrtError::NonConvergence`] instead of looping
+/// forever.
+#[cfg_attr(any(feature = "soft-float", feature = "hardware"), allow(dead_code))]
+pub(crate) fn newton_sqrt<T: SqrtFloat>(number: T) -> Result<T, SqrtError> {
+    newton_sqrt_with(number, T::TOLERANCE, T::ZERO, None)
+}
+
+/// [`newton_sqrt`] with caller-chosen stopping rules, giving up with
+/// [`SqrtError::DidNotConverge`] after `max_iterations` steps if set.
+///
+/// The iteration stops once the step between guesses is below `tolerance`, or at most
+/// `relative_tolerance` times the current guess, so that accuracy scales with the magnitude of
+/// the root. Pass zero to disable either rule.
+#[cfg_attr(any(feature = "soft-float", feature = "hardware"), allow(dead_code))]
+pub(crate) fn newton_sqrt_with<T: SqrtFloat>(
+    number: T,
+    tolerance: T,
+    relative_tolerance: T,
+    max_iterations: Option<u32>,
+) -> Result<T, SqrtError> {
+    if number < T::ZERO {
+        return Err(SqrtError::NegativeNumber(number.into()));
+    }
+
+    // `0 / 0` would poison the first step with NaN.
+    if number == T::ZERO {
+        return Ok(number);
+    }
+
+    let mut guess = number.initial_guess();
+    let mut prev_guess;
+    let mut prev_step = T::INFINITY;
+    let mut iterations = 0u32;
+
+    loop {
+        prev_guess = guess;
+        guess = (guess + number / guess) / T::TWO;
+        iterations = iterations.saturating_add(1);
+
+        let step = (prev_guess - guess).abs();
+        if step < tolerance || step <= relative_tolerance * guess {
+            break;
+        }
+        if step.is_nan() || step >= prev_step {
+            return Err(SqrtError::NonConvergence {
+                iterations,
+                residual: (guess * guess - number).abs().into(),
+            });
+        }
+        if Some(iterations) == max_iterations {
+            return Err(SqrtError::DidNotConverge {
+                iterations,
+                last_residual: (guess * guess - number).abs().into(),
+            });
+        }
+        prev_step = step;
+    }
+
+    Ok(guess)
+}
+
+/// Halley's method, which triples the number of correct digits per step where Newton–Raphson
+/// doubles them, at the cost of an extra division.
+///
+/// The iteration `x ← x · (x² + 3a) / (3x² + a)` is evaluated through the ratio `a / x²`, so it
+/// never squares a large guess. It starts from the exponent-halving guess of
+/// [`SqrtFloat::initial_guess`], so every magnitude converges in a few steps. Inputs, stopping
+/// and stall rules are those of [`newton_sqrt_with`], except that a step of a few ulps also
+/// counts as converged.
+fn halley_sqrt(
+    number: f64,
+    tolerance: f64,
+    relative_tolerance: f64,
+    max_iterations: Option<u32>,
+) -> Result<f64, SqrtError> {
+    if number < 0.0 {
+        return Err(SqrtError::NegativeNumber(number));
+    }
+    if number == 0.0 {
+        return Ok(number);
+    }
+    if !number.is_finite() {
+        return Err(SqrtError::NonConvergence {
+            iterations: 1,
+            residual: f64::NAN,
+        });
+    }
+
+    let mut guess = number.initial_guess();
+    let mut prev_step = f64::INFINITY;
+    let mut iterations = 0u32;
+
+    loop {
+        let ratio = number / guess / guess;
+        let next = guess * (1.0 + 3.0 * ratio) / (3.0 + ratio);
+        iterations = iterations.saturating_add(1);
+
+        let step = (guess - next).abs();
+        guess = next;
+        // A step of one or two ulps is the precision limit: near the root Halley can alternate
+        // between neighbouring floats, which the stall check would otherwise report.
+        if step < tolerance || step <= guess * relative_tolerance.max(4.0 * f64::EPSILON) {
+            break;
+        }
+        if step.is_nan() || step >= prev_step {
+            return Err(SqrtError::NonConvergence {
+                iterations,
+                residual: (guess * guess - number).abs(),
+            });
+        }
+        if Some(iterations) == max_iterations {
+            return Err(SqrtError::DidNotConverge {
+                iterations,
+                last_residual: (guess * guess - number).abs(),
+            });
+        }
+        prev_step = step;
+    }
+
+    Ok(guess)
+}
+
+/// Bisection of `[0, max(a, 1)]`, which always brackets the root.
+///
+/// Slow (one bit per step) but monotone: the bracket only ever shrinks, so the estimate cannot
+/// overshoot or oscillate. It stops once the bracket is narrower than `tolerance` or
+/// `relative_tolerance` times its lower end, or cannot be split any further, and returns its
+/// midpoint. Inputs and the iteration cap are handled as in [`newton_sqrt_with`].
+fn bisection_sqrt(
+    number: f64,
+    tolerance: f64,
+    relative_tolerance: f64,
+    max_iterations: Option<u32>,
+) -> Result<f64, SqrtError> {
+    if number < 0.0 {
+        return Err(SqrtError::NegativeNumber(number));
+    }
+    if number == 0.0 {
+        return Ok(number);
+    }
+    if !number.is_finite() {
+        return Err(SqrtError::NonConvergence {
+            iterations: 1,
+            residual: f64::NAN,
+        });
+    }
+
+    let (mut low, mut high) = (0.0, number.max(1.0));
+    let mut iterations = 0u32;
+
+    loop {
+        let middle = low + (high - low) / 2.0;
+        iterations = iterations.saturating_add(1);
+        if middle <= low || middle >= high {
+            break;
+        }
+        if middle * middle > number {
+            high = middle;
+        } else {
+            low = middle;
+        }
+        if high - low < tolerance || high - low <= relative_tolerance * low {
+            break;
+        }
+        if Some(iterations) == max_iterations {
+            return Err(SqrtError::DidNotConverge {
+                iterations,
+                last_residual: (middle * middle - number).abs(),
+            });
+        }
+    }
+
+    Ok(low + (high - low) / 2.0)
+}
+
+/// Single-precision Newton–Raphson square root, tuned for speed.
+///
+/// The starting guess halves the exponent directly on the bit pattern, so it is within a few
+/// percent of the root, and the iteration stops once a step is below one `f32` epsilon relative
+/// to the guess. This reaches single precision in about three steps for any input magnitude.
+/// Subnormal inputs are scaled by an exact power of four first. Stalls are reported as
+/// [`SqrtError::NonConvergence`], as in [`newton_sqrt`].
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub(crate) fn newton_sqrt_f32(number: f32) -> Result<f32, SqrtError> {
+    if number < 0.0 {
+        return Err(SqrtError::NegativeNumber(number.into()));
+    }
+    if number == 0.0 {
+        return Ok(number);
+    }
+    if !number.is_finite() {
+        return Err(SqrtError::NonConvergence {
+            iterations: 1,
+            residual: f64::NAN,
+        });
+    }
+    // Scaling by 2^24 makes every subnormal normal; the root is then scaled back by 2^-12.
+    let (number, unscale) = if number < f32::MIN_POSITIVE {
+        (number * 16_777_216.0, 1.0 / 4096.0)
+    } else {
+        (number, 1.0)
+    };
+
+    let mut guess = f32::from_bits((number.to_bits() >> 1) + 0x1fbd_1df5);
+    let mut prev_guess;
+    let mut prev_step = f32::INFINITY;
+    let mut iterations = 0u32;
+
+    loop {
+        prev_guess = guess;
+        guess = (guess + number / guess) / 2.0;
+        iterations = iterations.saturating_add(1);
+
+        let step = (prev_guess - guess).abs();
+        if step <= guess * f32::EPSILON {
+            break;
+        }
+        if step.is_nan() || step >= prev_step {
+            return Err(SqrtError::NonConvergence {
+                iterations,
+                residual: (guess * guess - number).abs().into(),
+            });
+        }
+        prev_step = step;
+    }
+
+    Ok(guess * unscale)
+}
+
+/// Inverse square root `1/√x`, iterated directly so no division follows the root.
+///
+/// The starting guess is the classic subtract-the-halved-bits trick on the IEEE-754 pattern,
+/// within 0.2% of the result, and each Newton step `y ← y · (3 − x·y²) / 2` is division-free.
+/// The iteration stops once a step is within the few epsilons of rounding noise the update
+/// carries, in about four steps for any input magnitude. Subnormal inputs are scaled by an exact
+/// power of four first. Zero gives an infinity with its sign, like `1.0 / 0.0`; negative, `NaN`
+/// and infinite inputs fail as in [`newton_sqrt`].
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub(crate) fn inverse_sqrt(number: f64) -> Result<f64, SqrtError> {
+    if number < 0.0 {
+        return Err(SqrtError::NegativeNumber(number));
+    }
+    if number == 0.0 {
+        return Ok(1.0 / number);
+    }
+    if !number.is_finite() {
+        return Err(SqrtError::NonConvergence {
+            iterations: 1,
+            residual: f64::NAN,
+        });
+    }
+    // Scaling by 2^54 makes every subnormal normal; the result is then scaled back by 2^27.
+    let (number, unscale) = if number < f64::MIN_POSITIVE {
+        (number * 18_014_398_509_481_984.0, 134_217_728.0)
+    } else {
+        (number, 1.0)
+    };
+
+    let mut guess = f64::from_bits(0x5fe6_eb50_c7b5_37a9 - (number.to_bits() >> 1));
+    let mut prev_guess;
+    let mut prev_step = f64::INFINITY;
+    let mut iterations = 0u32;
+
+    loop {
+        prev_guess = guess;
+        // `x·y·y` rather than `x·y²`, so the square of a huge estimate never overflows.
+        guess *= 1.5 - 0.5 * number * guess * guess;
+        iterations = iterations.saturating_add(1);
+
+        let step = (prev_guess - guess).abs();
+        if step <= guess * (4.0 * f64::EPSILON) {
+            break;
+        }
+        if step.is_nan() || step >= prev_step {
+            return Err(SqrtError::NonConvergence {
+                iterations,
+                residual: (number * guess * guess - 1.0).abs(),
+            });
+        }
+        prev_step = step;
+    }
+
+    Ok(guess * unscale)
+}
+
+/// Real cube root, defined for negative numbers as well: `∛−8 = −2`.
+///
+/// The starting guess divides the IEEE-754 bit pattern by three and re-biases the exponent,
+/// within 4% of the root, and the Newton step `y ← (2y + x/y²) / 3` stops once it is within a
+/// few epsilons relative to the estimate, so every magnitude converges in about five steps.
+/// Subnormal inputs are scaled by an exact power of eight first. `NaN` and infinite inputs fail
+/// as in [`newton_sqrt`].
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub(crate) fn cbrt(number: f64) -> Result<f64, SqrtError> {
+    if number == 0.0 {
+        return Ok(number);
+    }
+    if !number.is_finite() {
+        return Err(SqrtError::NonConvergence {
+            iterations: 1,
+            residual: f64::NAN,
+        });
+    }
+    let (magnitude, sign) = (number.abs(), number);
+    // Scaling by 2^54 makes every subnormal normal; the root is then scaled back by 2^-18.
+    let (magnitude, unscale) = if magnitude < f64::MIN_POSITIVE {
+        (magnitude * 18_014_398_509_481_984.0, 1.0 / 262_144.0)
+    } else {
+        (magnitude, 1.0)
+    };
+
+    let mut guess = f64::from_bits(magnitude.to_bits() / 3 + 0x2a9f_7893_782d_a1ce);
+    let mut prev_guess;
+    let mut prev_step = f64::INFINITY;
+    let mut iterations = 0u32;
+
+    loop {
+        prev_guess = guess;
+        guess = (2.0 * guess + magnitude / (guess * guess)) / 3.0;
+        iterations = iterations.saturating_add(1);
+
+        let step = (prev_guess - guess).abs();
+        if step <= guess * (4.0 * f64::EPSILON) {
+            break;
+        }
+        if step.is_nan() || step >= prev_step {
+            return Err(SqrtError::NonConvergence {
+                iterations,
+                residual: (guess * guess * guess - magnitude).abs(),
+            });
+        }
+        prev_step = step;
+    }
+
+    Ok((guess * unscale).copysign(sign))
+}
+
+/// Splits a positive finite `number` into a mantissa in `[1, 2)` and a binary exponent,
+/// normalising subnormals.
+fn split_exponent(number: f64) -> (f64, i64) {
+    // Scaling by 2^54 makes every subnormal normal.
+    let (number, offset) = if number < f64::MIN_POSITIVE {
+        (number * 18_014_398_509_481_984.0, -54)
+    } else {
+        (number, 0)
+    };
+    let bits = number.to_bits();
+    let mantissa = f64::from_bits(bits & ((1 << 52) - 1) | 0x3ff0_0000_0000_0000);
+    (mantissa, (bits >> 52) as i64 - 1023 + offset)
+}
+
+/// Returns `value · 2^exponent`, saturating to zero or infinity far outside the `f64` range.
+fn scale_by_power_of_two(value: f64, exponent: i64) -> f64 {
+    let pow2 = |exponent: i64| f64::from_bits(((exponent + 1023) as u64) << 52);
+    let exponent = exponent.clamp(-2000, 2000);
+    value * pow2(exponent / 2) * pow2(exponent - exponent / 2)
+}
+
+/// Returns `base^exponent` for a positive finite `base` as a mantissa and a binary exponent, so
+/// that no intermediate power overflows or underflows, whatever the degree.
+fn split_powu(base: f64, mut exponent: u32) -> (f64, i64) {
+    let (mut mantissa, mut scale) = (1.0, 0i64);
+    let (mut base_mantissa, mut base_scale) = split_exponent(base);
+    loop {
+        if exponent & 1 == 1 {
+            let (product, carry) = split_exponent(mantissa * base_mantissa);
+            mantissa = product;
+            scale += base_scale + carry;
+        }
+        exponent >>= 1;
+        if exponent == 0 {
+            return (mantissa, scale);
+        }
+        let (square, carry) = split_exponent(base_mantissa * base_mantissa);
+        base_mantissa = square;
+        base_scale = 2 * base_scale + carry;
+    }
+}
+
+/// Generalised Newton kernel for the real `degree`-th root.
+///
+/// Odd roots of negative numbers are the negated roots of their magnitudes, `∛−8 = −2`; even
+/// roots of negative numbers fail with [`SqrtError::NegativeNumber`].
+///
+/// The starting guess `2^(log₂ x / n)` is within a few hundred ulps of the root, and each step
+/// `y ← y · (1 + (x / yⁿ − 1) / n)` keeps `x` and `yⁿ` as mantissa and exponent, so subnormal
+/// inputs and degrees up to `u32::MAX` neither overflow nor lose precision. The iteration stops
+/// once a step is within a few epsilons relative to the estimate, like [`cbrt`]. `NaN` and
+/// infinite inputs fail as in [`newton_sqrt`].
+pub(crate) fn newton_nth_root(number: f64, degree: u32) -> Result<f64, SqrtError> {
+    if degree == 0 {
+        return Err(SqrtError::InvalidDegree(degree));
+    }
+    if number < 0.0 && degree.is_multiple_of(2) {
+        return Err(SqrtError::NegativeNumber(number));
+    }
+    if number == 0.0 || degree == 1 {
+        return Ok(number);
+    }
+    if !number.is_finite() {
+        return Err(SqrtError::NonConvergence {
+            iterations: 1,
+            residual: f64::NAN,
+        });
+    }
+    let (magnitude, sign) = (number.abs(), number);
+    let (mantissa, exponent) = split_exponent(magnitude);
+
+    let k = f64::from(degree);
+    let mut guess = (magnitude.log2() / k).exp2();
+    let mut prev_guess;
+    let mut prev_step = f64::INFINITY;
+    let mut iterations = 0u32;
+
+    loop {
+        let (power, scale) = split_powu(guess, degree);
+        let ratio = scale_by_power_of_two(mantissa / power, exponent - scale);
+        prev_guess = guess;
+        guess *= 1.0 + (ratio - 1.0) / k;
+        iterations = iterations.saturating_add(1);
+
+        let step = (prev_guess - guess).abs();
+        if step <= guess * (4.0 * f64::EPSILON) {
+            break;
+        }
+        if step.is_nan() || step >= prev_step {
+            let (power, scale) = split_powu(guess, degree);
+            return Err(SqrtError::NonConvergence {
+                iterations,
+                residual: (scale_by_power_of_two(power, scale) - magnitude).abs(),
+            });
+        }
+        prev_step = step;
+    }
+
+    Ok(guess.copysign(sign))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_newton_sqrt_with() {
+        let exact = newton_sqrt_with(1e10, 1e-10, 0.0, None).unwrap();
+        let rough = newton_sqrt_with(1e10, 1.0, 0.0, None).unwrap();
+        assert!((exact - 1e5).abs() < 1e-10);
+        assert!((rough - 1e5).abs() < 1.0);
+
+        assert!(newton_sqrt_with(1e10, 1e-10, 0.0, Some(64)).is_ok());
+        assert!(matches!(
+            newton_sqrt_with(1e10, 1e-10, 0.0, Some(3)),
+            Err(SqrtError::DidNotConverge { iterations: 3, last_residual }) if last_residual > 0.0
+        ));
+    }
+
+    #[test]
+    fn test_relative_tolerance_scales_with_magnitude() {
+        // Compares algorithms, which an `only-*` feature replaces with its own.
+        if ONLY_ALGORITHM.is_some() {
+            return;
+        }
+        let relative = SqrtOptions::new().relative_tolerance(1e-12);
+        for algorithm in [Algorithm::Newton, Algorithm::Halley, Algorithm::Bisection] {
+            let options = relative.clone().algorithm(algorithm);
+            for number in [1e-310, 1e-300, 1e-20, 2.0, 1e20, 1e300] {
+                let root = sqrt_with(number, &options).unwrap();
+                let expected = number.sqrt();
+                assert!(
+                    (root - expected).abs() <= 1e-12 * expected,
+                    "{algorithm:?}: √{number} = {root}"
+                );
+            }
+        }
+
+        // The absolute default stops long before the root of a tiny input is accurate.
+        let absolute = SqrtOptions::new().algorithm(Algorithm::Newton);
+        let root = sqrt_with(1e-300, &absolute).unwrap();
+        assert!((root - 1e-150).abs() > 1e-12 * 1e-150);
+        // An explicit absolute tolerance still applies alongside the relative one.
+        let both = relative.tolerance(1e-3).algorithm(Algorithm::Newton);
+        assert!((sqrt_with(2.0, &both).unwrap() - 2f64.sqrt()).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_relative_sqrt() {
+        for number in [f64::from_bits(1), 1e-300, 1e-20, 0.5, 2.0, 1e20, f64::MAX] {
+            let root = relative_sqrt(number).unwrap();
+            let expected = number.sqrt();
+            assert!(
+                (root - expected).abs() <= 4.0 * f64::EPSILON * expected,
+                "√{number} = {root}"
+            );
+        }
+        assert_eq!(relative_sqrt(0.0).unwrap(), 0.0);
+        assert!(relative_sqrt(-1.0).is_err());
+        assert!(relative_sqrt(f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_newton_sqrt_converges_quickly_at_any_magnitude() {
+        for number in [0.3, 7.0, 1e100, 1e300, f64::MAX] {
+            let root = newton_sqrt_with(number, f64::TOLERANCE, 0.0, Some(6)).unwrap();
+            assert!((root - number.sqrt()).abs() <= f64::EPSILON * number.sqrt());
+        }
+        for number in [1e-30f32, 0.3, 7.0, 1e30, f32::MAX] {
+            newton_sqrt_with(number, f32::TOLERANCE, 0.0, Some(6)).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_inverse_sqrt() {
+        let values = [
+            f64::from_bits(1),
+            f64::MIN_POSITIVE / 3.0,
+            1e-300,
+            0.01,
+            0.5,
+            1.0,
+            2.0,
+            3.0,
+            1e10,
+            1e300,
+            f64::MAX,
+        ];
+        for value in values {
+            let inverse = inverse_sqrt(value).unwrap();
+            let expected = 1.0 / value.sqrt();
+            assert!(
+                (inverse - expected).abs() <= 4.0 * f64::EPSILON * expected,
+                "1/√{value} = {inverse}, expected {expected}"
+            );
+        }
+        assert_eq!(inverse_sqrt(4.0).unwrap(), 0.5);
+
+        for bits in crate::fixtures::random_bits(10_000) {
+            let value = f64::from_bits(bits >> 1);
+            if value.is_finite() {
+                let inverse = inverse_sqrt(value).unwrap();
+                let expected = 1.0 / value.sqrt();
+                assert!(
+                    (inverse - expected).abs() <= 4.0 * f64::EPSILON * expected,
+                    "1/√{value} = {inverse}, expected {expected}"
+                );
+            }
+        }
+
+        assert_eq!(inverse_sqrt(0.0).unwrap(), f64::INFINITY);
+        assert_eq!(inverse_sqrt(-0.0).unwrap(), f64::NEG_INFINITY);
+        assert!(matches!(
+            inverse_sqrt(-1.0),
+            Err(SqrtError::NegativeNumber(-1.0))
+        ));
+        for value in [f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                inverse_sqrt(value),
+                Err(SqrtError::NonConvergence { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_cbrt() {
+        let mut values = vec![
+            f64::from_bits(1),
+            f64::MIN_POSITIVE / 3.0,
+            1e-300,
+            0.001,
+            2.0,
+            1e300,
+            f64::MAX,
+        ];
+        values.extend(crate::fixtures::random_bits(10_000).map(f64::from_bits));
+        for value in values.into_iter().filter(|value| value.is_finite()) {
+            let root = cbrt(value).unwrap();
+            let expected = value.cbrt();
+            assert!(
+                (root - expected).abs() <= 2.0 * f64::EPSILON * expected.abs(),
+                "∛{value} = {root}, expected {expected}"
+            );
+        }
+        assert_eq!(cbrt(27.0).unwrap(), 3.0);
+        assert_eq!(cbrt(-8.0).unwrap(), -2.0);
+        assert_eq!(cbrt(-0.0).unwrap().to_bits(), (-0.0f64).to_bits());
+        for value in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert!(matches!(cbrt(value), Err(SqrtError::NonConvergence { .. })));
+        }
+    }
+
+    #[test]
+    fn test_algorithms_share_contract() {
+        let algorithms = [
+            Algorithm::Newton,
+            Algorithm::Halley,
+            Algorithm::Bisection,
+            Algorithm::Hardware,
+        ];
+        for algorithm in algorithms {
+            let options = SqrtOptions::new().algorithm(algorithm);
+            for number in [1e-300, 1e-8, 0.25, 2.0, 1e10, 1e300, f64::MAX] {
+                let root = sqrt_with(number, &options).unwrap();
+                let expected = number.sqrt();
+                assert!(
+                    (root - expected).abs() <= 1e-10_f64.max(4.0 * f64::EPSILON * expected),
+                    "{algorithm:?}: √{number} = {root}"
+                );
+            }
+            assert_eq!(sqrt_with(0.0, &options).unwrap(), 0.0);
+            assert!(matches!(
+                sqrt_with(-4.0, &options),
+                Err(SqrtError::NegativeNumber(-4.0))
+            ));
+            for number in [f64::NAN, f64::INFINITY] {
+                assert!(matches!(
+                    sqrt_with(number, &options),
+                    Err(SqrtError::NonConvergence { .. })
+                ));
+            }
+        }
+
+        if ONLY_ALGORITHM.is_some() {
+            return;
+        }
+        assert_eq!(
+            sqrt_with(2.0, &SqrtOptions::new().algorithm(Algorithm::Hardware)).unwrap(),
+            2f64.sqrt()
+        );
+        let capped = SqrtOptions::new().max_iterations(2);
+        for algorithm in [Algorithm::Newton, Algorithm::Halley, Algorithm::Bisection] {
+            assert!(matches!(
+                sqrt_with(1e10, &capped.clone().algorithm(algorithm)),
+                Err(SqrtError::DidNotConverge { iterations: 2, .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_only_feature_fixes_the_algorithm() {
+        let Some(only) = ONLY_ALGORITHM else {
+            return;
+        };
+        for number in [0.5, 2.0, 1e10] {
+            let expected = run_algorithm(only, number, default_tolerance(only), 0.0, None).unwrap();
+            assert_eq!(sqrt(number).unwrap(), expected);
+            for algorithm in [
+                Algorithm::Newton,
+                Algorithm::Halley,
+                Algorithm::Bisection,
+                Algorithm::Hardware,
+            ] {
+                let options = SqrtOptions::new().algorithm(algorithm);
+                assert_eq!(sqrt_with(number, &options).unwrap(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_algorithms_share_contract_on_random_inputs() {
+        let numbers: Vec<f64> = crate::fixtures::random_bits(10_000)
+            .map(|bits| f64::from_bits(bits >> 1))
+            .filter(|number| number.is_finite())
+            .collect();
+        for algorithm in [Algorithm::Newton, Algorithm::Halley, Algorithm::Bisection] {
+            let absolute = SqrtOptions::new().algorithm(algorithm);
+            let relative = absolute.clone().relative_tolerance(f64::EPSILON);
+            for (options, tolerance) in [(absolute, 1e-10_f64), (relative, 0.0)] {
+                for &number in &numbers {
+                    let root = sqrt_with(number, &options)
+                        .unwrap_or_else(|error| panic!("{algorithm:?}: √{number}: {error}"));
+                    let expected = number.sqrt();
+                    assert!(
+                        (root - expected).abs() <= tolerance.max(4.0 * f64::EPSILON * expected),
+                        "{algorithm:?}: √{number} = {root}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_newton_nth_root() {
+        for (number, degree, expected) in [
+            (27.0, 3, 3.0),
+            (1024.0, 10, 2.0),
+            (1e-30, 5, 1e-6),
+            (7.0, 1, 7.0),
+            (-27.0, 3, -3.0),
+            (-7.0, 1, -7.0),
+            (-1e-30, 5, -1e-6),
+        ] {
+            let root = newton_nth_root(number, degree).unwrap();
+            assert!(
+                (root - expected).abs() < 1e-10,
+                "{number}^(1/{degree}) = {root}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_newton_nth_root_matches_powf() {
+        for bits in crate::fixtures::random_bits(10_000) {
+            let number = f64::from_bits(bits);
+            let degree = 2 + (bits >> 58) as u32;
+            if !number.is_finite() || (number < 0.0 && degree.is_multiple_of(2)) {
+                continue;
+            }
+            let root = newton_nth_root(number, degree).unwrap();
+            let expected = number.abs().powf(1.0 / f64::from(degree)).copysign(number);
+            // `powf` itself is off by the rounding of `1/n`, magnified by `ln |x| / n`.
+            let slack = 4.0 + number.abs().ln().abs() / f64::from(degree);
+            assert!(
+                (root - expected).abs() <= slack * f64::EPSILON * expected.abs(),
+                "{number}^(1/{degree}) = {root}, expected {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_newton_nth_root_extremes() {
+        let root = newton_nth_root(4.2880822751e16, 3).unwrap();
+        assert!((root - 4.2880822751e16f64.cbrt()).abs() <= 2.0 * f64::EPSILON * root);
+        let root = newton_nth_root(f64::from_bits(1), 3).unwrap();
+        assert!((root - f64::from_bits(1).cbrt()).abs() <= 2.0 * f64::EPSILON * root);
+        let root = newton_nth_root(f64::MAX, 2).unwrap();
+        assert!((root - f64::MAX.sqrt()).abs() <= 2.0 * f64::EPSILON * root);
+
+        for number in [2.0, f64::from_bits(1), f64::MAX] {
+            for degree in [1 << 20, i32::MAX as u32, u32::MAX] {
+                let root = newton_nth_root(number, degree).unwrap();
+                let expected = (number.ln() / f64::from(degree)).exp();
+                assert!(
+                    (root - expected).abs() <= 4.0 * f64::EPSILON,
+                    "{number}^(1/{degree}) = {root}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_newton_sqrt_f32() {
+        for number in [f32::from_bits(1), 1e-30, 0.5, 2.0, 12345.0, 1e30, f32::MAX] {
+            let root = newton_sqrt_f32(number).unwrap();
+            let expected = number.sqrt();
+            assert!(
+                (root - expected).abs() <= expected * 2.0 * f32::EPSILON,
+                "√{number} = {root}"
+            );
+        }
+        assert_eq!(newton_sqrt_f32(0.0).unwrap(), 0.0);
+        assert!(newton_sqrt_f32(-1.0).is_err());
+        assert!(newton_sqrt_f32(f32::NAN).is_err());
+        assert!(newton_sqrt_f32(f32::INFINITY).is_err());
+    }
+
+    #[test]
+    fn test_newton_nth_root_invalid() {
+        assert!(matches!(
+            newton_nth_root(8.0, 0),
+            Err(SqrtError::InvalidDegree(0))
+        ));
+        assert!(matches!(
+            newton_nth_root(-8.0, 4),
+            Err(SqrtError::NegativeNumber(_))
+        ));
+        assert!(matches!(
+            newton_nth_root(f64::NEG_INFINITY, 3),
+            Err(SqrtError::NonConvergence { .. })
+        ));
+    }
+}
@@ -1,100 +1,425 @@
+// Without `anyhow` the crate error type is `SqrtError` itself, so the `Ok(result?)` and
+// `.into()` conversions used throughout become identities.
+#![cfg_attr(
+    not(feature = "anyhow"),
+    allow(clippy::needless_question_mark, clippy::useless_conversion)
+)]
+
+#[cfg(feature = "ndarray")]
+mod array;
+mod batch;
+#[cfg(feature = "half")]
+mod bfloat;
+mod dual;
+mod edge;
 mod error;
+mod expr;
+#[cfg(feature = "fixed")]
+mod fixed_point;
+pub mod fixtures;
+mod float;
+#[doc(hidden)]
+pub mod fuzz;
+mod histogram;
+mod hw;
+mod interval;
+mod isqrt;
+mod iter;
+mod kernel;
+#[cfg(feature = "nalgebra")]
+mod linalg;
+mod measurement;
+mod mixed;
+mod norm;
+mod options;
+mod order;
+#[cfg(feature = "highprec")]
+mod precise;
+mod preprocess;
+#[cfg(feature = "quad")]
+mod quad;
+mod quantize;
+#[cfg(feature = "rational")]
+mod rational;
+mod reduce;
+mod request;
+mod select;
+mod signal;
+#[cfg(feature = "simd")]
+mod simd;
+mod sink;
+// Also the fallback of `hw` on targets without a square root instruction, and the kernel of
+// `square_root_exact`.
+mod soft;
+mod stats;
+mod stochastic;
+mod telemetry;
+mod vec;
+
+#[cfg(feature = "ndarray")]
+pub use array::{sqrt_array, sqrt_array_inplace};
+pub use batch::{BatchReport, ValidationReport};
+#[cfg(feature = "half")]
+pub use bfloat::{row_norms_bf16, square_roots_bf16};
+pub use dual::{square_root_dual, Dual};
+pub use edge::{EdgeCase, EdgeCaseReport};
+pub use error::{Error, Result, SqrtError};
+pub use expr::Expr;
+#[cfg(feature = "fixed")]
+pub use fixed_point::FixedSqrt;
+pub use float::SqrtFloat;
+pub use histogram::{sqrt_histogram, sqrt_histogram_with, Histogram};
+pub use interval::{square_root_interval, Interval};
+#[cfg(feature = "bigint")]
+pub use isqrt::isqrt_big;
+pub use isqrt::{
+    is_perfect_square, isqrt_rem, isqrt_u128, isqrt_u64, perfect_square_root, sqrt_of_len, ISqrt,
+};
+pub use iter::{SqrtChunks, SqrtIter};
+#[cfg(feature = "nalgebra")]
+pub use linalg::{cholesky_sqrt, matrix_sqrt, whitening_transform};
+pub use measurement::{square_root_with_uncertainty, Measurement};
+#[cfg(feature = "nalgebra")]
+pub use norm::{frobenius_norm, frobenius_norm_with};
+pub use norm::{frobenius_norm_rows, frobenius_norm_rows_with, rms, rms_with};
+pub use options::{Algorithm, ExecutionBackend, OverloadPolicy, SqrtOptions};
+pub use order::SortOrder;
+#[cfg(feature = "highprec")]
+pub use precise::square_root_precise;
+pub use preprocess::{l2_normalize_rows, minmax_scale, sanitize, SanitizePolicy, SanitizeReport};
+#[cfg(feature = "quad")]
+pub use quad::square_root_quad;
+pub use quantize::{Quantization, Quantize};
+#[cfg(feature = "rational")]
+pub use rational::square_root_rational;
+pub use reduce::Reduction;
+pub use request::{BatchLimits, BatchRequest};
+pub use select::{sqrt_filter, sqrt_filter_with, sqrt_top_k, sqrt_top_k_with};
+pub use signal::{amplitude_to_db, power_to_db};
+#[cfg(feature = "complex")]
+pub use signal::{complex_magnitudes, complex_magnitudes_db, square_root_complex};
+#[cfg(feature = "simd")]
+pub use simd::{square_roots_simd, square_roots_simd_f32};
+pub use sink::ResultSink;
+#[cfg(feature = "sled")]
+pub use sink::SledSink;
+#[cfg(feature = "sqlite")]
+pub use sink::SqliteSink;
+pub use stats::{
+    iqr_sigma, mad_sigma, median, standardize, std_dev, std_dev_confidence_interval, std_dev_with,
+    variance, VarianceAlgorithm, MAD_SCALE, NORMAL_IQR,
+};
+pub use stochastic::RandomSource;
+#[cfg(feature = "tracing")]
+pub use telemetry::TracingTelemetry;
+pub use telemetry::{NoopTelemetry, TelemetrySink};
+pub use vec::{SqrtVec, VecStats};
 
-pub use error::SqrtError;
+use std::sync::atomic::AtomicBool;
 
-use anyhow::Result;
+#[cfg(feature = "async")]
 use tokio::task;
 
+#[cfg(feature = "async")]
 /// Computes the square root of a number asynchronously by offloading the computation to a blocking thread pool.
 ///
+/// Generic over [`SqrtFloat`], so both `square_root_async::<f32>` and `square_root_async::<f64>`
+/// are available; float literals default to `f64`.
+///
 /// # Arguments
 /// - `number`: The input number (must be non-negative).
 ///
 /// # Returns
-/// - `Ok(f64)` if the computation is successful.
-/// - `Err(anyhow::Error)` if the input number is negative.
-pub async fn square_root_async(number: f64) -> Result<f64> {
-    task::spawn_blocking(move || {
-        if number < 0.0 {
-            return Err(SqrtError::NegativeNumber(number))?;
-        }
+/// - `Ok(T)` if the computation is successful.
+/// - `Err(anyhow::Error)` if the input number is negative or the iteration fails to converge.
+pub async fn square_root_async<T: SqrtFloat>(number: T) -> Result<T> {
+    task::spawn_blocking(move || Ok(number.kernel_sqrt()?)).await?
+}
+
+#[cfg(feature = "async")]
+/// Computes the square roots of a list of numbers asynchronously using parallel processing for heavy workloads.
+///
+/// # Arguments
+/// - `numbers`: A vector of numbers (all must be non-negative).
+///
+/// # Returns
+/// - `Ok(Vec<f64>)` if all computations are successful.
+/// - `Err(anyhow::Error)` if any input number is negative or fails to converge.
+pub async fn square_roots_parallel(numbers: Vec<f64>) -> Result<Vec<f64>> {
+    let (results, _) = batch::run_batch_async(numbers, SqrtOptions::default()).await?;
+    Ok(results)
+}
 
-        let mut guess = number / 2.0;
-        let mut prev_guess;
+#[cfg(feature = "async")]
+/// Computes the square roots of a list of numbers asynchronously, applying the given options.
+///
+/// # Arguments
+/// - `numbers`: A vector of numbers (all must be non-negative).
+/// - `options`: The options controlling the computation.
+///
+/// # Returns
+/// - `Ok((Vec<f64>, BatchReport))` with the results and a summary of the batch.
+/// - `Err(anyhow::Error)` if any input number is negative or fails to converge, or the batch
+///   cannot finish before `options.deadline` ([`SqrtError::DeadlineExceeded`] with the partial
+///   results).
+pub async fn square_roots_parallel_with(
+    numbers: Vec<f64>,
+    options: &SqrtOptions,
+) -> Result<(Vec<f64>, BatchReport)> {
+    batch::run_batch_async(numbers, options.clone()).await
+}
 
-        loop {
-            prev_guess = guess;
-            guess = (guess + number / guess) / 2.0;
-            if (prev_guess - guess).abs() < 1e-10 {
-                break;
-            }
+#[cfg(feature = "async")]
+/// Computes the square roots of a list of numbers on the calling async task, yielding to the
+/// executor after every `slice_len` elements.
+///
+/// Nothing is offloaded to a blocking pool, which makes this suitable for single-threaded
+/// executors (e.g. on WASM) where `spawn_blocking` is unavailable, while still letting other
+/// tasks run during long batches.
+///
+/// # Arguments
+/// - `numbers`: A vector of numbers (all must be non-negative).
+/// - `slice_len`: The number of elements computed between yields (at least 1).
+///
+/// # Returns
+/// - `Ok(Vec<f64>)` if all computations are successful.
+/// - `Err(anyhow::Error)` if any input number is negative or fails to converge.
+pub async fn square_roots_cooperative(numbers: Vec<f64>, slice_len: usize) -> Result<Vec<f64>> {
+    let mut results = Vec::with_capacity(numbers.len());
+    for slice in numbers.chunks(slice_len.max(1)) {
+        for &number in slice {
+            results.push(kernel::sqrt(number)?);
         }
+        task::yield_now().await;
+    }
 
-        Ok(guess)
-    })
-    .await?
+    Ok(results)
 }
 
-/// Computes the square roots of a list of numbers asynchronously using parallel processing for heavy workloads.
+#[cfg(feature = "async")]
+/// Computes the inverse square root `1/√x` of a number asynchronously. See
+/// [`inverse_square_root`].
+///
+/// # Arguments
+/// - `number`: The input number (must be non-negative).
+///
+/// # Returns
+/// - `Ok(f64)` if the computation is successful.
+/// - `Err(anyhow::Error)` if the input number is negative or the iteration fails to converge.
+pub async fn inverse_square_root_async(number: f64) -> Result<f64> {
+    task::spawn_blocking(move || Ok(kernel::inverse_sqrt(number)?)).await?
+}
+
+#[cfg(feature = "async")]
+/// Computes the inverse square roots of a list of numbers asynchronously, offloading the batch to
+/// a blocking thread pool. See [`inverse_square_roots_parallel_sync`].
 ///
 /// # Arguments
 /// - `numbers`: A vector of numbers (all must be non-negative).
 ///
 /// # Returns
 /// - `Ok(Vec<f64>)` if all computations are successful.
-/// - `Err(anyhow::Error)` if any input number is negative.
-pub async fn square_roots_parallel(numbers: Vec<f64>) -> Result<Vec<f64>> {
-    task::spawn_blocking(move || {
-        numbers
-            .into_iter()
-            .map(|number| {
-                if number < 0.0 {
-                    return Err(SqrtError::NegativeNumber(number))?;
-                }
-
-                let mut guess = number / 2.0;
-                let mut prev_guess;
+/// - `Err(anyhow::Error)` if any input number is negative or fails to converge.
+pub async fn inverse_square_roots_parallel(numbers: Vec<f64>) -> Result<Vec<f64>> {
+    task::spawn_blocking(move || inverse_square_roots_parallel_sync(numbers)).await?
+}
 
-                loop {
-                    prev_guess = guess;
-                    guess = (guess + number / guess) / 2.0;
-                    if (prev_guess - guess).abs() < 1e-10 {
-                        break;
-                    }
-                }
+#[cfg(feature = "async")]
+/// Computes the real cube root of a number asynchronously. See [`cube_root`].
+///
+/// # Arguments
+/// - `number`: The input number.
+///
+/// # Returns
+/// - `Ok(f64)` if the computation is successful.
+/// - `Err(anyhow::Error)` if the input number is `NaN` or infinite, or the iteration fails to
+///   converge.
+pub async fn cube_root_async(number: f64) -> Result<f64> {
+    task::spawn_blocking(move || Ok(kernel::cbrt(number)?)).await?
+}
 
-                Ok(guess)
-            })
-            .collect()
-    })
-    .await?
+#[cfg(feature = "async")]
+/// Computes the real cube roots of a list of numbers asynchronously, offloading the batch to a
+/// blocking thread pool. See [`cube_roots_parallel_sync`].
+///
+/// # Arguments
+/// - `numbers`: A vector of numbers.
+///
+/// # Returns
+/// - `Ok(Vec<f64>)` if all computations are successful.
+/// - `Err(anyhow::Error)` if any input number is `NaN` or infinite, or fails to converge.
+pub async fn cube_roots_parallel(numbers: Vec<f64>) -> Result<Vec<f64>> {
+    task::spawn_blocking(move || cube_roots_parallel_sync(numbers)).await?
 }
 
 /// Computes the square root of a number synchronously.
 ///
+/// Generic over [`SqrtFloat`], so both `square_root::<f32>` and `square_root::<f64>` are
+/// available; float literals default to `f64`.
+///
 /// # Arguments
 /// - `number`: The input number (must be non-negative).
 ///
 /// # Returns
+/// - `Ok(T)` if the computation is successful.
+/// - `Err(anyhow::Error)` if the input number is negative or the iteration fails to converge.
+pub fn square_root<T: SqrtFloat>(number: T) -> Result<T> {
+    Ok(number.kernel_sqrt()?)
+}
+
+/// Computes the square root of a number synchronously with the given options.
+///
+/// The per-element options apply as they do in batches: subnormal flushing, the
+/// [`algorithm`](SqrtOptions::algorithm) with its [`tolerance`](SqrtOptions::tolerance) and
+/// [`max_iterations`](SqrtOptions::max_iterations), and clamping. Batch-only options such as
+/// the backend, deadline and telemetry are ignored.
+///
+/// # Arguments
+/// - `number`: The input number (must be non-negative).
+/// - `options`: The options to apply.
+///
+/// # Returns
 /// - `Ok(f64)` if the computation is successful.
-/// - `Err(anyhow::Error)` if the input number is negative.
-pub fn square_root(number: f64) -> Result<f64> {
-    if number < 0.0 {
-        return Err(SqrtError::NegativeNumber(number))?;
-    }
+/// - `Err(anyhow::Error)` if the input number is negative, the iteration fails to converge, or
+///   it hits the configured cap ([`SqrtError::DidNotConverge`]).
+///
+/// ```
+/// use sqrtx::SqrtOptions;
+///
+/// let options = SqrtOptions::new().tolerance(1e-3).max_iterations(50);
+/// let root = sqrtx::square_root_with(2.0, &options)?;
+/// assert!((root - 2f64.sqrt()).abs() < 1e-3);
+/// # Ok::<(), sqrtx::Error>(())
+/// ```
+pub fn square_root_with(number: f64, options: &SqrtOptions) -> Result<f64> {
+    Ok(batch::sqrt_element(
+        number,
+        options,
+        &mut BatchReport::default(),
+    )?)
+}
 
-    let mut guess = number / 2.0;
-    let mut prev_guess;
+/// Computes the correctly rounded square root of a number synchronously.
+///
+/// Unlike [`square_root`], whose Newton–Raphson kernel stops at an absolute tolerance and can be
+/// off in the last bits, the result is always the `f64` nearest to the exact root (ties to even),
+/// the same value IEEE-754 requires of [`f64::sqrt`]. It is computed with integer arithmetic
+/// only, so it does not depend on the target's floating-point unit either.
+///
+/// # Arguments
+/// - `number`: The input number (must be non-negative and finite).
+///
+/// # Returns
+/// - `Ok(f64)` within half an ulp of `√number`.
+/// - `Err(anyhow::Error)` if the input number is negative, infinite or `NaN`, like [`square_root`].
+///
+/// ```
+/// let root = sqrtx::square_root_exact(1e300)?;
+/// assert_eq!(root, 1e300f64.sqrt());
+/// # Ok::<(), sqrtx::Error>(())
+/// ```
+pub fn square_root_exact(number: f64) -> Result<f64> {
+    Ok(kernel::exact_sqrt(number)?)
+}
 
-    loop {
-        prev_guess = guess;
-        guess = (guess + number / guess) / 2.0;
-        if (prev_guess - guess).abs() < 1e-10 {
-            break;
-        }
-    }
+/// Computes the inverse square root `1/√x` of a number synchronously.
+///
+/// This is the hot operation when normalizing vectors. It runs its own division-free Newton
+/// iteration on `1/√x` instead of dividing by [`square_root`], and is accurate to a few ulps.
+/// Zero gives an infinity with its sign, as `1.0 / 0.0` would.
+///
+/// # Arguments
+/// - `number`: The input number (must be non-negative).
+///
+/// # Returns
+/// - `Ok(f64)` if the computation is successful.
+/// - `Err(anyhow::Error)` if the input number is negative or the iteration fails to converge.
+///
+/// ```
+/// let (x, y) = (3.0, 4.0);
+/// let scale = sqrtx::inverse_square_root(x * x + y * y)?;
+/// assert!((x * scale - 0.6).abs() < 1e-15);
+/// # Ok::<(), sqrtx::Error>(())
+/// ```
+pub fn inverse_square_root(number: f64) -> Result<f64> {
+    Ok(kernel::inverse_sqrt(number)?)
+}
 
-    Ok(guess)
+/// Computes the inverse square roots of a list of numbers synchronously, in parallel for heavy
+/// workloads like [`square_roots_parallel_sync`].
+///
+/// # Arguments
+/// - `numbers`: A vector of numbers (all must be non-negative).
+///
+/// # Returns
+/// - `Ok(Vec<f64>)` if all computations are successful.
+/// - `Err(anyhow::Error)` if any input number is negative or fails to converge.
+pub fn inverse_square_roots_parallel_sync(numbers: Vec<f64>) -> Result<Vec<f64>> {
+    let (results, _) =
+        batch::run_batch_kernel(&numbers, &SqrtOptions::default(), kernel::inverse_sqrt)?;
+    Ok(results)
+}
+
+/// Computes the real cube root of a number synchronously.
+///
+/// Unlike square roots, cube roots are defined for every real number: `cube_root(-8.0)` is
+/// `-2`. The dedicated kernel is accurate to a couple of ulps at any magnitude. See
+/// [`nth_root`] for other degrees.
+///
+/// # Arguments
+/// - `number`: The input number.
+///
+/// # Returns
+/// - `Ok(f64)` if the computation is successful.
+/// - `Err(anyhow::Error)` if the input number is `NaN` or infinite, or the iteration fails to
+///   converge.
+///
+/// ```
+/// assert_eq!(sqrtx::cube_root(-27.0)?, -3.0);
+/// # Ok::<(), sqrtx::Error>(())
+/// ```
+pub fn cube_root(number: f64) -> Result<f64> {
+    Ok(kernel::cbrt(number)?)
+}
+
+/// Computes the real cube roots of a list of numbers synchronously, in parallel for heavy
+/// workloads like [`square_roots_parallel_sync`].
+///
+/// # Arguments
+/// - `numbers`: A vector of numbers.
+///
+/// # Returns
+/// - `Ok(Vec<f64>)` if all computations are successful.
+/// - `Err(anyhow::Error)` if any input number is `NaN` or infinite, or fails to converge.
+pub fn cube_roots_parallel_sync(numbers: Vec<f64>) -> Result<Vec<f64>> {
+    let (results, _) = batch::run_batch_kernel(&numbers, &SqrtOptions::default(), kernel::cbrt)?;
+    Ok(results)
+}
+
+/// Computes the real `degree`-th root of a number synchronously, with a generalised Newton
+/// iteration.
+///
+/// Odd roots are defined for every real number: a negative input gives the negated root of its
+/// magnitude, so `nth_root(-8.0, 3)` is `-2`. Even roots of negative numbers fail like
+/// [`square_root`].
+///
+/// # Arguments
+/// - `number`: The input number (must be non-negative if `degree` is even).
+/// - `degree`: The degree of the root (must be positive).
+///
+/// # Returns
+/// - `Ok(f64)` if the computation is successful.
+/// - `Err(anyhow::Error)` if `degree` is zero ([`SqrtError::InvalidDegree`]), `number` is
+///   negative under an even degree, or the iteration fails to converge.
+///
+/// ```
+/// let root = sqrtx::nth_root(-32.0, 5)?;
+/// assert!((root + 2.0).abs() < 1e-10);
+/// assert!(sqrtx::nth_root(-16.0, 4).is_err());
+/// # Ok::<(), sqrtx::Error>(())
+/// ```
+pub fn nth_root(number: f64, degree: u32) -> Result<f64> {
+    Ok(kernel::newton_nth_root(number, degree)?)
 }
 
 /// Computes the square roots of a list of numbers synchronously using parallel processing for heavy workloads.
@@ -104,32 +429,344 @@ pub fn square_root(number: f64) -> Result<f64> {
 ///
 /// # Returns
 /// - `Ok(Vec<f64>)` if all computations are successful.
-/// - `Err(anyhow::Error)` if any input number is negative.
+/// - `Err(anyhow::Error)` if any input number is negative or fails to converge.
 pub fn square_roots_parallel_sync(numbers: Vec<f64>) -> Result<Vec<f64>> {
+    let (results, _) = batch::run_batch(numbers, &SqrtOptions::default())?;
+    Ok(results)
+}
+
+/// Computes the square roots of a list of numbers synchronously, applying the given options.
+///
+/// # Arguments
+/// - `numbers`: A vector of numbers (all must be non-negative).
+/// - `options`: The options controlling the computation.
+///
+/// # Returns
+/// - `Ok((Vec<f64>, BatchReport))` with the results and a summary of the batch.
+/// - `Err(anyhow::Error)` if any input number is negative or fails to converge.
+pub fn square_roots_parallel_sync_with(
+    numbers: Vec<f64>,
+    options: &SqrtOptions,
+) -> Result<(Vec<f64>, BatchReport)> {
+    Ok(batch::run_batch(numbers, options)?)
+}
+
+/// Checks a batch without computing it, counting the inputs that would fail (negative, `NaN` or
+/// infinite) or be flushed to zero under `options`.
+///
+/// This is a cheap single scan, useful for validating a dataset before a long run.
+///
+/// # Arguments
+/// - `numbers`: The numbers that would be passed to a batch API.
+/// - `options`: The options the batch would run with.
+///
+/// # Returns
+/// A [`ValidationReport`] with the counts.
+pub fn validate_batch(numbers: &[f64], options: &SqrtOptions) -> ValidationReport {
+    batch::validate(numbers, options)
+}
+
+/// Computes the square root of an `f32` with the single-precision fast path.
+///
+/// Unlike [`square_root::<f32>`](square_root), this uses a bit-level starting guess and a
+/// tolerance relative to `f32` precision, so it needs only a few iterations at any magnitude.
+/// Results are within one or two ulps of the correctly rounded root.
+///
+/// # Arguments
+/// - `number`: The input number (must be non-negative).
+///
+/// # Returns
+/// - `Ok(f32)` if the computation is successful.
+/// - `Err(anyhow::Error)` if the input number is negative or the iteration fails to converge.
+pub fn square_root_f32(number: f32) -> Result<f32> {
+    Ok(kernel::newton_sqrt_f32(number)?)
+}
+
+#[cfg(feature = "async")]
+/// Computes the square roots of `f32` numbers asynchronously with the single-precision fast
+/// path, keeping data in `f32` throughout.
+///
+/// # Arguments
+/// - `numbers`: A vector of numbers (all must be non-negative).
+///
+/// # Returns
+/// - `Ok(Vec<f32>)` if all computations are successful.
+/// - `Err(anyhow::Error)` if any input number is negative or fails to converge.
+pub async fn square_roots_parallel_f32(numbers: Vec<f32>) -> Result<Vec<f32>> {
+    task::spawn_blocking(move || square_roots_parallel_sync_f32(&numbers)).await?
+}
+
+/// Computes the square roots of `f32` numbers synchronously with the single-precision fast
+/// path, keeping data in `f32` throughout.
+///
+/// # Arguments
+/// - `numbers`: The input numbers (all must be non-negative).
+///
+/// # Returns
+/// - `Ok(Vec<f32>)` if all computations are successful.
+/// - `Err(anyhow::Error)` if any input number is negative or fails to converge.
+pub fn square_roots_parallel_sync_f32(numbers: &[f32]) -> Result<Vec<f32>> {
+    let (results, _) = batch::run_batch_f32_fast(numbers, &SqrtOptions::default())?;
+    Ok(results)
+}
+
+/// Computes the square roots of `f32` numbers in `f64` precision, applying the given options.
+///
+/// Inputs are widened losslessly and the roots are returned as `f64`, for pipelines that store
+/// data in `f32` but need accurate results downstream.
+///
+/// # Arguments
+/// - `numbers`: The input numbers (all must be non-negative).
+/// - `options`: The options controlling the computation. `flush_denormals` applies to `f32`
+///   subnormal inputs.
+///
+/// # Returns
+/// - `Ok((Vec<f64>, BatchReport))` with the results and a summary of the batch.
+/// - `Err(anyhow::Error)` if any input number is negative or fails to converge.
+pub fn square_roots_f32_sync_with(
+    numbers: &[f32],
+    options: &SqrtOptions,
+) -> Result<(Vec<f64>, BatchReport)> {
+    Ok(batch::run_batch_f32(numbers, options)?)
+}
+
+/// Computes the correctly rounded `f32` square roots of `f32` numbers, applying the given options.
+///
+/// Each root is computed in `f64` and rounded to the nearest `f32`, matching IEEE-754 `f32`
+/// square root exactly.
+///
+/// # Arguments
+/// - `numbers`: The input numbers (all must be non-negative).
+/// - `options`: The options controlling the computation. `flush_denormals` applies to `f32`
+///   subnormal inputs.
+///
+/// # Returns
+/// - `Ok((Vec<f32>, BatchReport))` with the results and a summary of the batch.
+/// - `Err(anyhow::Error)` if any input number is negative or fails to converge.
+pub fn square_roots_f32_rounded_sync_with(
+    numbers: &[f32],
+    options: &SqrtOptions,
+) -> Result<(Vec<f32>, BatchReport)> {
+    Ok(batch::run_batch_f32_rounded(numbers, options)?)
+}
+
+/// Computes square roots and quantizes them to a compact integer type in a single pass, applying
+/// the given options.
+///
+/// Each root goes through the same pipeline as [`square_roots_parallel_sync_with`] and is then
+/// mapped to `round(root / scale) + zero_point`, saturating at the bounds of `Q`.
+///
+/// # Arguments
+/// - `numbers`: The input numbers (all must be non-negative).
+/// - `options`: The options controlling the computation.
+/// - `quantization`: The scale and zero point of the integer encoding.
+///
+/// # Returns
+/// - `Ok((Vec<Q>, BatchReport))` with the quantized roots and a summary of the batch, whose
+///   `clamped` count includes the values that saturated.
+/// - `Err(anyhow::Error)` if the scale is not positive and finite
+///   ([`SqrtError::InvalidScale`]), or any input number is negative or fails to converge.
+///
+/// ```
+/// use sqrtx::{Quantization, SqrtOptions};
+///
+/// let quantization = Quantization::new(0.1, 0);
+/// let (values, _) =
+///     sqrtx::square_roots_quantized_sync_with::<u8>(&[4.0, 2.0], &SqrtOptions::new(), quantization)?;
+/// assert_eq!(values, [20, 14]);
+/// # Ok::<(), sqrtx::Error>(())
+/// ```
+pub fn square_roots_quantized_sync_with<Q: Quantize>(
+    numbers: &[f64],
+    options: &SqrtOptions,
+    quantization: Quantization,
+) -> Result<(Vec<Q>, BatchReport)> {
+    Ok(batch::run_batch_quantized(numbers, options, &quantization)?)
+}
+
+/// Computes square roots in `f64` and stochastically rounds them to `f32`.
+///
+/// Each root is rounded up or down to a neighbouring `f32` with probability proportional to its
+/// distance from them, so repeated accumulation of the outputs is unbiased. The computation is
+/// sequential because every element draws from `rng`.
+///
+/// # Arguments
+/// - `numbers`: The input numbers (all must be non-negative).
+/// - `rng`: The source of random bits used for rounding.
+///
+/// # Returns
+/// - `Ok(Vec<f32>)` if all computations are successful.
+/// - `Err(anyhow::Error)` if any input number is negative or fails to converge.
+pub fn square_roots_stochastic_f32<R: RandomSource + ?Sized>(
+    numbers: &[f64],
+    rng: &mut R,
+) -> Result<Vec<f32>> {
     numbers
-        .into_iter()
-        .map(|number| {
-            if number < 0.0 {
-                return Err(SqrtError::NegativeNumber(number))?;
-            }
-
-            let mut guess = number / 2.0;
-            let mut prev_guess;
-
-            loop {
-                prev_guess = guess;
-                guess = (guess + number / guess) / 2.0;
-                if (prev_guess - guess).abs() < 1e-10 {
-                    break;
-                }
-            }
-
-            Ok(guess)
-        })
+        .iter()
+        .map(|&number| Ok(stochastic::round_f32(kernel::sqrt(number)?, rng)))
         .collect()
 }
 
-#[cfg(test)]
+/// Computes the square roots of a list of numbers synchronously, stopping early once `cancel` is
+/// set.
+///
+/// The flag is checked before each chunk of work is started, so cancellation takes effect within
+/// one chunk's computation time. Set it from another thread (e.g. a GUI event handler).
+///
+/// # Arguments
+/// - `numbers`: A vector of numbers (all must be non-negative).
+/// - `cancel`: The cancellation flag.
+///
+/// # Returns
+/// - `Ok(Vec<f64>)` if all computations are successful.
+/// - `Err(anyhow::Error)` if any input number is negative or fails to converge, or
+///   [`SqrtError::Cancelled`] if the flag was set before the batch finished.
+pub fn square_roots_parallel_sync_with_cancel(
+    numbers: Vec<f64>,
+    cancel: &AtomicBool,
+) -> Result<Vec<f64>> {
+    let (results, _) =
+        batch::run_batch_cancellable(&numbers, &SqrtOptions::default(), Some(cancel))?;
+    Ok(results)
+}
+
+/// Computes square roots and returns `(input, root)` pairs sorted by root.
+///
+/// The sort is stable (equal roots keep their input order) and runs in parallel on the rayon
+/// backends.
+///
+/// # Arguments
+/// - `numbers`: A vector of numbers (all must be non-negative).
+/// - `options`: The options controlling the computation.
+/// - `order`: Whether the smallest or the largest root comes first.
+///
+/// # Returns
+/// - `Ok((Vec<(f64, f64)>, BatchReport))` with the sorted pairs and a summary of the batch.
+/// - `Err(anyhow::Error)` if any input number is negative or fails to converge.
+pub fn square_roots_sorted(
+    numbers: Vec<f64>,
+    options: &SqrtOptions,
+    order: SortOrder,
+) -> Result<(Vec<(f64, f64)>, BatchReport)> {
+    Ok(order::sorted(numbers, options, order)?)
+}
+
+/// Computes square roots and returns `(input, root)` pairs stable-sorted by a caller-supplied
+/// key of each pair.
+///
+/// # Arguments
+/// - `numbers`: A vector of numbers (all must be non-negative).
+/// - `options`: The options controlling the computation.
+/// - `key`: Maps `(input, root)` to the sort key.
+///
+/// # Returns
+/// - `Ok((Vec<(f64, f64)>, BatchReport))` with the sorted pairs and a summary of the batch.
+/// - `Err(anyhow::Error)` if any input number is negative or fails to converge.
+pub fn square_roots_sorted_by_key<K, F>(
+    numbers: Vec<f64>,
+    options: &SqrtOptions,
+    key: F,
+) -> Result<(Vec<(f64, f64)>, BatchReport)>
+where
+    K: Ord,
+    F: Fn(f64, f64) -> K + Sync,
+{
+    Ok(order::sorted_by_key(numbers, options, key)?)
+}
+
+/// Computes square roots and streams them into a [`ResultSink`], keyed by input position.
+///
+/// # Arguments
+/// - `numbers`: The input numbers (all must be non-negative).
+/// - `sink`: The destination for the results.
+///
+/// # Returns
+/// - `Ok(usize)` with the number of results written.
+/// - `Err(anyhow::Error)` if any input number is negative, fails to converge, or the sink fails.
+///   Results computed before the failure have already been written.
+pub fn square_roots_into_sink<S: ResultSink>(
+    numbers: impl IntoIterator<Item = f64>,
+    sink: &mut S,
+) -> Result<usize> {
+    square_roots_keyed_into_sink((0..).zip(numbers), sink)
+}
+
+/// Computes square roots and streams them into a [`ResultSink`], keyed by caller-supplied ids.
+///
+/// # Arguments
+/// - `items`: `(key, number)` pairs (all numbers must be non-negative).
+/// - `sink`: The destination for the results.
+///
+/// # Returns
+/// - `Ok(usize)` with the number of results written.
+/// - `Err(anyhow::Error)` if any input number is negative, fails to converge, or the sink fails.
+///   Results computed before the failure have already been written.
+pub fn square_roots_keyed_into_sink<S: ResultSink>(
+    items: impl IntoIterator<Item = (u64, f64)>,
+    sink: &mut S,
+) -> Result<usize> {
+    let mut written = 0;
+    for (key, number) in items {
+        sink.write(key, kernel::sqrt(number)?)
+            .map_err(error::external)?;
+        written += 1;
+    }
+    sink.flush().map_err(error::external)?;
+
+    Ok(written)
+}
+
+/// Computes square roots lazily, one per item pulled from the returned iterator.
+///
+/// Nothing is computed up front, so pipelines that stop early (`find`, `take_while`, `?` in a
+/// loop) only pay for the items they consume. Errors are yielded in place rather than ending the
+/// iteration.
+///
+/// The returned [`SqrtIter`] is double-ended and exact-size whenever the input's iterator is.
+///
+/// # Arguments
+/// - `numbers`: The input numbers (all should be non-negative).
+///
+/// # Returns
+/// - A [`SqrtIter`] of `Result<f64, SqrtError>`, one per input, in input order.
+///
+/// ```
+/// let first_large = sqrtx::sqrt_iter([4.0, 9.0, 100.0, -1.0])
+///     .find(|root| matches!(root, Ok(root) if *root > 5.0));
+/// assert!(matches!(first_large, Some(Ok(root)) if (root - 10.0).abs() < 1e-10));
+/// ```
+pub fn sqrt_iter<I: IntoIterator<Item = f64>>(numbers: I) -> SqrtIter<I::IntoIter> {
+    SqrtIter::new(numbers.into_iter())
+}
+
+/// Computes square roots lazily in chunks, so callers can interleave their own per-chunk work
+/// (writing, validation) with the computation without materializing the whole batch.
+///
+/// # Arguments
+/// - `numbers`: The input numbers (all should be non-negative).
+/// - `chunk_size`: The number of roots per chunk (at least 1). The last chunk may be shorter.
+///
+/// # Returns
+/// - A [`SqrtChunks`] yielding `Ok(Vec<f64>)` per chunk, in input order, or
+///   `Err(anyhow::Error)` for a chunk containing a negative number or one that fails to
+///   converge. Iteration continues with the next chunk after an error.
+///
+/// ```
+/// let mut total = 0;
+/// for chunk in sqrtx::sqrt_chunks((0..10).map(f64::from), 4) {
+///     total += chunk?.len();
+/// }
+/// assert_eq!(total, 10);
+/// # Ok::<(), sqrtx::Error>(())
+/// ```
+pub fn sqrt_chunks<I: IntoIterator<Item = f64>>(
+    numbers: I,
+    chunk_size: usize,
+) -> SqrtChunks<I::IntoIter> {
+    SqrtChunks::new(numbers.into_iter(), chunk_size)
+}
+
+#[cfg(all(test, feature = "async"))]
 mod tests {
     use super::*;
     use tokio::runtime::Runtime;
@@ -142,6 +779,40 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_square_roots_parallel_f32() -> Result<()> {
+        let rt = Runtime::new().unwrap();
+        let results = rt.block_on(square_roots_parallel_f32(vec![4.0, 16.0, 2.0]))?;
+        assert_eq!(results[..2], [2.0, 4.0]);
+        assert!((results[2] - std::f32::consts::SQRT_2).abs() <= 2.0 * f32::EPSILON);
+        assert!(rt.block_on(square_roots_parallel_f32(vec![-1.0])).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_inverse_square_roots_async() -> Result<()> {
+        let rt = Runtime::new().unwrap();
+        assert_eq!(rt.block_on(inverse_square_root_async(16.0))?, 0.25);
+        let results = rt.block_on(inverse_square_roots_parallel(vec![4.0, 0.25]))?;
+        assert_eq!(results, [0.5, 2.0]);
+        assert!(rt
+            .block_on(inverse_square_roots_parallel(vec![1.0, -1.0]))
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_cube_roots_async() -> Result<()> {
+        let rt = Runtime::new().unwrap();
+        assert_eq!(rt.block_on(cube_root_async(-64.0))?, -4.0);
+        let results = rt.block_on(cube_roots_parallel(vec![8.0, -0.125, 0.0]))?;
+        assert_eq!(results, [2.0, -0.5, 0.0]);
+        assert!(rt
+            .block_on(cube_roots_parallel(vec![1.0, f64::NAN]))
+            .is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_square_roots_parallel() -> Result<()> {
         let rt = Runtime::new().unwrap();
@@ -157,6 +828,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(feature = "tiny", ignore = "checks the message text")]
     fn test_square_root_async_negative() {
         let rt = Runtime::new().unwrap();
         let result = rt.block_on(square_root_async(-4.0));
@@ -169,6 +841,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(feature = "tiny", ignore = "checks the message text")]
     fn test_square_roots_parallel_with_negative() {
         let rt = Runtime::new().unwrap();
         let numbers = vec![4.0, -16.0, 25.0];
@@ -180,6 +853,33 @@ mod tests {
             "Cannot calculate the square root of a negative number: -16"
         );
     }
+
+    #[test]
+    fn test_square_roots_cooperative() -> anyhow::Result<()> {
+        let rt = tokio::runtime::Builder::new_current_thread().build()?;
+        let results = rt.block_on(square_roots_cooperative(vec![4.0, 9.0, 16.0], 2))?;
+        assert_eq!(results, vec![2.0, 3.0, 4.0]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_square_roots_cooperative_yields() {
+        use futures::FutureExt;
+
+        let future = square_roots_cooperative(vec![1.0; 10], 3);
+        assert!(future.now_or_never().is_none());
+    }
+
+    #[test]
+    fn test_square_roots_parallel_with_flush_denormals() -> Result<()> {
+        let rt = Runtime::new().unwrap();
+        let options = SqrtOptions::new().flush_denormals(true);
+        let numbers = vec![4.0, f64::MIN_POSITIVE / 4.0, 25.0];
+        let (results, report) = rt.block_on(square_roots_parallel_with(numbers, &options))?;
+        assert_eq!(results[1], 0.0);
+        assert_eq!(report.flushed, 1);
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -207,6 +907,41 @@ mod tests_sync {
     }
 
     #[test]
+    fn test_nth_root() -> Result<()> {
+        assert!((nth_root(81.0, 4)? - 3.0).abs() < 1e-10);
+        assert!((nth_root(-1e9, 3)? + 1e3).abs() < 1e-10);
+        assert_eq!(nth_root(-0.0, 3)?.to_bits(), (-0.0f64).to_bits());
+        assert!(nth_root(-4.0, 2).is_err());
+        assert!(nth_root(4.0, 0).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_cube_roots_parallel_sync() -> Result<()> {
+        let numbers: Vec<f64> = (-500..=500).map(|i| f64::from(i * i * i)).collect();
+        let results = cube_roots_parallel_sync(numbers)?;
+        for (i, root) in (-500..=500).zip(results) {
+            assert!((root - f64::from(i)).abs() <= 2.0 * f64::EPSILON * f64::from(i).abs());
+        }
+        assert!(cube_root(f64::INFINITY).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_inverse_square_roots_parallel_sync() -> Result<()> {
+        let numbers: Vec<f64> = (1..=1000).map(f64::from).collect();
+        let results = inverse_square_roots_parallel_sync(numbers.clone())?;
+        for (number, inverse) in numbers.into_iter().zip(results) {
+            assert!((inverse * number.sqrt() - 1.0).abs() <= 4.0 * f64::EPSILON);
+        }
+        assert_eq!(inverse_square_root(0.0)?, f64::INFINITY);
+        assert!(inverse_square_root(-4.0).is_err());
+        assert!(inverse_square_roots_parallel_sync(vec![4.0, f64::NAN]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    #[cfg_attr(feature = "tiny", ignore = "checks the message text")]
     fn test_square_root_sync_negative() {
         let result = square_root(-4.0);
         assert!(result.is_err());
@@ -218,6 +953,159 @@ mod tests_sync {
     }
 
     #[test]
+    fn test_square_roots_f32_sync_with() -> Result<()> {
+        let numbers = [4.0_f32, 2.0, 1e-30];
+        let (wide, report) = square_roots_f32_sync_with(&numbers, &SqrtOptions::default())?;
+        assert_eq!(report.elements, 3);
+        assert!((wide[1] - std::f64::consts::SQRT_2).abs() < 1e-15);
+        assert!((wide[2] - f64::from(1e-30_f32).sqrt()).abs() < 1e-30);
+
+        let (rounded, _) = square_roots_f32_rounded_sync_with(&numbers, &SqrtOptions::default())?;
+        assert_eq!(
+            rounded,
+            vec![2.0, std::f32::consts::SQRT_2, 1e-30_f32.sqrt()]
+        );
+        assert!(square_roots_f32_rounded_sync_with(&[-1.0], &SqrtOptions::default()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_square_roots_stochastic_f32() -> Result<()> {
+        let roots = square_roots_stochastic_f32(&[4.0, 2.0], &mut || 0)?;
+        assert_eq!(roots[0], 2.0);
+        // A zero draw always rounds up.
+        assert!(f64::from(roots[1]) > std::f64::consts::SQRT_2);
+        assert!(square_roots_stochastic_f32(&[-1.0], &mut || 0).is_err());
+        Ok(())
+    }
+
+    #[test]
+    #[cfg_attr(feature = "tiny", ignore = "checks the message text")]
+    fn test_square_roots_parallel_sync_with_cancel() -> Result<()> {
+        let cancel = AtomicBool::new(false);
+        let results = square_roots_parallel_sync_with_cancel(vec![4.0, 9.0], &cancel)?;
+        assert_eq!(results, vec![2.0, 3.0]);
+
+        cancel.store(true, std::sync::atomic::Ordering::Relaxed);
+        let error = square_roots_parallel_sync_with_cancel(vec![4.0, 9.0], &cancel).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Computation cancelled after 0 of 2 values"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_square_root_generic() -> Result<()> {
+        let single: f32 = square_root::<f32>(2.0)?;
+        assert!((single - std::f32::consts::SQRT_2).abs() <= f32::EPSILON);
+        assert!((square_root::<f64>(2.0)? - std::f64::consts::SQRT_2).abs() < 1e-10);
+        assert!(square_root(-4.0_f32).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_square_root_f32() -> Result<()> {
+        assert_eq!(square_root_f32(9.0)?, 3.0);
+        assert_eq!(
+            square_roots_parallel_sync_f32(&[1.0, 0.25])?,
+            vec![1.0, 0.5]
+        );
+        assert!(square_root_f32(-9.0).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_sqrt_iter_is_lazy() {
+        let mut computed = 0;
+        let numbers = [1.0, 4.0, -9.0, 16.0]
+            .into_iter()
+            .inspect(|_| computed += 1);
+        let roots: Vec<_> = sqrt_iter(numbers).take(3).collect();
+        assert_eq!(computed, 3);
+        assert!(matches!(roots[1], Ok(root) if (root - 2.0).abs() < 1e-10));
+        assert!(matches!(roots[2], Err(SqrtError::NegativeNumber(-9.0))));
+    }
+
+    #[test]
+    fn test_square_root_sync_zero() -> Result<()> {
+        assert_eq!(square_root(0.0)?, 0.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_square_root_with() -> Result<()> {
+        assert_eq!(
+            square_root_with(1e10, &SqrtOptions::new())?,
+            square_root(1e10)?
+        );
+        let options = SqrtOptions::new().tolerance(0.5).max_iterations(100);
+        assert!((square_root_with(1e6, &options)? - 1e3).abs() < 0.5);
+        assert_eq!(
+            square_root_with(100.0, &SqrtOptions::new().clamp(0.0, 5.0))?,
+            5.0
+        );
+        assert!(square_root_with(-1.0, &options).is_err());
+
+        // The correctly rounded kernels have no iterations to cap.
+        #[cfg(not(any(
+            feature = "soft-float",
+            feature = "hardware",
+            feature = "only-newton",
+            feature = "only-halley",
+            feature = "only-bisection",
+            feature = "only-hardware"
+        )))]
+        {
+            let options = SqrtOptions::new().max_iterations(2);
+            let error = square_root_with(1e10, &options).unwrap_err();
+            assert!(matches!(
+                crate::error::as_sqrt_error(&error),
+                Some(SqrtError::DidNotConverge { iterations: 2, .. })
+            ));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_square_root_exact_matches_ieee() -> Result<()> {
+        let mut values = vec![
+            0.0,
+            -0.0,
+            f64::from_bits(1),
+            f64::MIN_POSITIVE,
+            2.0,
+            1e-300,
+            1e15 + 1.0,
+            1e300,
+            f64::MAX,
+        ];
+        values.extend(fixtures::random_bits(10_000).map(|bits| f64::from_bits(bits >> 1)));
+        for value in values.into_iter().filter(|value| value.is_finite()) {
+            let root = square_root_exact(value)?;
+            assert_eq!(root.to_bits(), value.sqrt().to_bits(), "√{value}");
+        }
+
+        assert!(square_root_exact(-1.0).is_err());
+        assert!(square_root_exact(f64::NAN).is_err());
+        assert!(square_root_exact(f64::INFINITY).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_square_root_sync_non_convergence() {
+        for number in [f64::NAN, f64::INFINITY] {
+            let result = square_root(number);
+            let error = result.unwrap_err();
+            assert!(matches!(
+                crate::error::as_sqrt_error(&error),
+                Some(SqrtError::NonConvergence { .. })
+            ));
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "tiny", ignore = "checks the message text")]
     fn test_square_roots_parallel_sync_with_negative() {
         let numbers = vec![4.0, -16.0, 25.0];
         let result = square_roots_parallel_sync(numbers);
@@ -228,4 +1116,43 @@ mod tests_sync {
             "Cannot calculate the square root of a negative number: -16"
         );
     }
+
+    #[test]
+    fn test_square_roots_parallel_sync_with_flush_denormals() -> Result<()> {
+        let options = SqrtOptions::new().flush_denormals(true);
+        let numbers = vec![f64::MIN_POSITIVE / 2.0, -f64::MIN_POSITIVE / 8.0, 16.0];
+        let (results, report) = square_roots_parallel_sync_with(numbers, &options)?;
+        assert_eq!(results[0], 0.0);
+        assert!(results[1] == 0.0 && results[1].is_sign_negative());
+        assert!((results[2] - 4.0).abs() < 1e-10);
+        assert_eq!(report.flushed, 2);
+        assert_eq!(report.elements, 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_square_roots_parallel_sync_with_keeps_denormals_by_default() -> Result<()> {
+        let numbers = vec![f64::MIN_POSITIVE / 2.0];
+        let (results, report) = square_roots_parallel_sync_with(numbers, &SqrtOptions::new())?;
+        assert!(results[0] > 0.0);
+        assert_eq!(report.flushed, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_square_roots_into_sink() -> Result<()> {
+        let mut sink = Vec::new();
+        let written = square_roots_into_sink(vec![4.0, 9.0], &mut sink)?;
+        assert_eq!(written, 2);
+        assert!((sink[1] - 3.0).abs() < 1e-10);
+        Ok(())
+    }
+
+    #[test]
+    fn test_square_roots_into_sink_with_negative() {
+        let mut sink = Vec::new();
+        let result = square_roots_into_sink(vec![4.0, -9.0, 16.0], &mut sink);
+        assert!(result.is_err());
+        assert_eq!(sink.len(), 1);
+    }
 }
@@ -0,0 +1,218 @@
+use crate::Result;
+use nalgebra::DMatrix;
+
+use crate::{kernel, SqrtError};
+
+/// Largest relative asymmetry `|aᵢⱼ − aⱼᵢ| / max|a|` still accepted as symmetric.
+const SYMMETRY_TOLERANCE: f64 = 1e-10;
+
+/// Ensures `matrix` is square and symmetric up to [`SYMMETRY_TOLERANCE`].
+fn check_symmetric(matrix: &DMatrix<f64>) -> Result<(), SqrtError> {
+    let (rows, cols) = matrix.shape();
+    if rows != cols {
+        return Err(SqrtError::NotSquare { rows, cols });
+    }
+
+    // Relative to the largest entry, so the zero matrix must be exactly symmetric.
+    let tolerance = SYMMETRY_TOLERANCE * matrix.amax();
+    for i in 0..rows {
+        for j in 0..i {
+            if (matrix[(i, j)] - matrix[(j, i)]).abs() > tolerance {
+                return Err(SqrtError::NotSymmetric);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Applies `f` to the eigenvalues of a symmetric positive-definite matrix, returning
+/// `V · diag(f(λ)) · Vᵀ`.
+///
+/// This is the shared machinery behind the eigenvalue-based matrix roots. `f` receives only
+/// strictly positive eigenvalues; anything at or below the rounding noise of the decomposition
+/// is reported as [`SqrtError::NotPositiveDefinite`].
+fn spd_eigen_map(
+    matrix: &DMatrix<f64>,
+    f: impl Fn(f64) -> Result<f64, SqrtError>,
+) -> Result<DMatrix<f64>, SqrtError> {
+    check_symmetric(matrix)?;
+    // nalgebra cannot decompose an empty matrix, whose every function is empty too.
+    if matrix.is_empty() {
+        return Ok(matrix.clone());
+    }
+
+    let eigen = matrix.clone().symmetric_eigen();
+    let threshold = f64::EPSILON * matrix.nrows() as f64 * eigen.eigenvalues.amax();
+    if eigen
+        .eigenvalues
+        .iter()
+        .any(|&lambda| lambda.is_nan() || lambda <= threshold)
+    {
+        return Err(SqrtError::NotPositiveDefinite);
+    }
+
+    let mapped = eigen
+        .eigenvalues
+        .iter()
+        .map(|&lambda| f(lambda))
+        .collect::<Result<Vec<_>, _>>()?;
+    let vectors = &eigen.eigenvectors;
+    let scaled = vectors * DMatrix::from_diagonal(&nalgebra::DVector::from_vec(mapped));
+    Ok(scaled * vectors.transpose())
+}
+
+/// Computes the principal square root of a symmetric positive-definite matrix.
+///
+/// The result is the unique symmetric positive-definite `S` with `S · S = A`, obtained by taking
+/// the square root of each eigenvalue. Use [`cholesky_sqrt`] instead when any factor with
+/// `L · Lᵀ = A` will do; it is cheaper.
+///
+/// Eigenvalue roots are correctly rounded whatever kernel the features select, so covariances
+/// in small units keep their precision.
+///
+/// # Arguments
+/// - `m`: A symmetric positive-definite matrix, such as a covariance matrix.
+///
+/// # Returns
+/// - `Ok(DMatrix<f64>)` with the symmetric square root.
+/// - `Err(anyhow::Error)` if the matrix is not square, not symmetric or not positive definite.
+pub fn matrix_sqrt(m: &DMatrix<f64>) -> Result<DMatrix<f64>> {
+    Ok(spd_eigen_map(m, kernel::exact_sqrt)?)
+}
+
+/// Computes the whitening transform `Σ^(−1/2)` of a covariance matrix.
+///
+/// Multiplying centred data by the result gives it identity covariance (ZCA whitening), which
+/// is the usual preprocessing step for PCA/ICA-style models.
+///
+/// # Arguments
+/// - `cov`: A symmetric positive-definite covariance matrix.
+///
+/// # Returns
+/// - `Ok(DMatrix<f64>)` with the symmetric inverse square root.
+/// - `Err(anyhow::Error)` if the matrix is not square, not symmetric or not positive definite.
+pub fn whitening_transform(cov: &DMatrix<f64>) -> Result<DMatrix<f64>> {
+    Ok(spd_eigen_map(cov, |lambda| {
+        Ok(1.0 / kernel::exact_sqrt(lambda)?)
+    })?)
+}
+
+/// Computes the Cholesky factor of a symmetric positive-definite matrix.
+///
+/// The result is the lower-triangular `L` with `L · Lᵀ = A`, the "square root" most statistics
+/// code wants (e.g. to draw correlated samples), as opposed to the symmetric principal root.
+///
+/// # Arguments
+/// - `matrix`: A symmetric positive-definite matrix.
+///
+/// # Returns
+/// - `Ok(DMatrix<f64>)` with the lower-triangular factor.
+/// - `Err(anyhow::Error)` if the matrix is not square, not symmetric or not positive definite.
+pub fn cholesky_sqrt(matrix: &DMatrix<f64>) -> Result<DMatrix<f64>> {
+    check_symmetric(matrix)?;
+    let cholesky = matrix
+        .clone()
+        .cholesky()
+        .ok_or(SqrtError::NotPositiveDefinite)?;
+    Ok(cholesky.unpack())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cholesky_sqrt() -> Result<()> {
+        let matrix = DMatrix::from_row_slice(3, 3, &[4.0, 2.0, 0.4, 2.0, 5.0, 1.0, 0.4, 1.0, 3.0]);
+        let factor = cholesky_sqrt(&matrix)?;
+        assert!((&factor * factor.transpose() - &matrix).amax() < 1e-10);
+        assert_eq!(factor[(0, 1)], 0.0);
+        assert_eq!(factor[(0, 0)], 2.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_matrix_sqrt() -> Result<()> {
+        let matrix = DMatrix::from_row_slice(3, 3, &[4.0, 2.0, 0.4, 2.0, 5.0, 1.0, 0.4, 1.0, 3.0]);
+        let root = matrix_sqrt(&matrix)?;
+        assert!((&root - root.transpose()).amax() < 1e-12);
+        assert!((&root * &root - &matrix).amax() < 1e-10);
+        assert!(root.clone().symmetric_eigen().eigenvalues.min() > 0.0);
+        assert!((&root * whitening_transform(&matrix)? - DMatrix::identity(3, 3)).amax() < 1e-10);
+
+        let diagonal = DMatrix::from_diagonal(&nalgebra::DVector::from_vec(vec![9.0, 16.0]));
+        let root = matrix_sqrt(&diagonal)?;
+        assert!((root - DMatrix::from_row_slice(2, 2, &[3.0, 0.0, 0.0, 4.0])).amax() < 1e-10);
+        assert!(matrix_sqrt(&DMatrix::from_row_slice(2, 2, &[1.0, 2.0, 2.0, 1.0])).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_small_scale_roots_keep_precision() -> Result<()> {
+        let variances = nalgebra::DVector::from_vec(vec![1e-20, 4e-20]);
+        let cov = DMatrix::from_diagonal(&variances);
+        let root = matrix_sqrt(&cov)?;
+        let whitening = whitening_transform(&cov)?;
+        for (i, expected) in [1e-10, 2e-10].into_iter().enumerate() {
+            assert!((root[(i, i)] - expected).abs() <= 4.0 * f64::EPSILON * expected);
+            let inverse = whitening[(i, i)] * expected;
+            assert!((inverse - 1.0).abs() <= 4.0 * f64::EPSILON, "{inverse}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_whitening_transform() -> Result<()> {
+        let cov = DMatrix::from_row_slice(2, 2, &[4.0, 1.0, 1.0, 3.0]);
+        let whitening = whitening_transform(&cov)?;
+        assert!((&whitening - whitening.transpose()).amax() < 1e-12);
+        assert!((&whitening * &cov * &whitening - DMatrix::identity(2, 2)).amax() < 1e-10);
+        Ok(())
+    }
+
+    #[test]
+    fn test_empty_matrix() -> Result<()> {
+        let empty = DMatrix::<f64>::zeros(0, 0);
+        assert_eq!(matrix_sqrt(&empty)?.shape(), (0, 0));
+        assert_eq!(whitening_transform(&empty)?.shape(), (0, 0));
+        assert_eq!(cholesky_sqrt(&empty)?.shape(), (0, 0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_whitening_transform_singular() {
+        let cov = DMatrix::from_row_slice(2, 2, &[1.0, 1.0, 1.0, 1.0]);
+        let error = whitening_transform(&cov).unwrap_err();
+        assert!(matches!(
+            crate::error::as_sqrt_error(&error),
+            Some(SqrtError::NotPositiveDefinite)
+        ));
+    }
+
+    #[test]
+    fn test_cholesky_sqrt_rejects_invalid_matrices() {
+        let error = |m: DMatrix<f64>| {
+            crate::error::into_sqrt_error(cholesky_sqrt(&m).unwrap_err()).unwrap()
+        };
+
+        assert!(matches!(
+            error(DMatrix::zeros(2, 3)),
+            SqrtError::NotSquare { rows: 2, cols: 3 }
+        ));
+        assert!(matches!(
+            error(DMatrix::from_row_slice(2, 2, &[1.0, 2.0, 0.0, 1.0])),
+            SqrtError::NotSymmetric
+        ));
+        assert!(matches!(
+            error(DMatrix::from_row_slice(2, 2, &[1.0, 2.0, 2.0, 1.0])),
+            SqrtError::NotPositiveDefinite
+        ));
+        // Asymmetry is judged relative to the entries, however small they are.
+        assert!(matches!(
+            error(DMatrix::from_row_slice(2, 2, &[1e-12, 5e-11, 0.0, 1e-12])),
+            SqrtError::NotSymmetric
+        ));
+        let tiny = DMatrix::from_row_slice(2, 2, &[4e-12, 1e-12, 1e-12 * (1.0 + 1e-12), 3e-12]);
+        assert!(cholesky_sqrt(&tiny).is_ok());
+    }
+}
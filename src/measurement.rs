@@ -0,0 +1,81 @@
+use crate::{kernel, Result};
+
+/// A value with its standard uncertainty, e.g. `value ± uncertainty` from a lab measurement.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Measurement {
+    /// The best estimate.
+    pub value: f64,
+    /// The standard uncertainty (one σ), non-negative.
+    pub uncertainty: f64,
+}
+
+impl Measurement {
+    /// Creates a measurement; the sign of `uncertainty` is ignored.
+    pub fn new(value: f64, uncertainty: f64) -> Self {
+        Self {
+            value,
+            uncertainty: uncertainty.abs(),
+        }
+    }
+
+    /// The uncertainty relative to the magnitude of the value.
+    pub fn relative_uncertainty(&self) -> f64 {
+        self.uncertainty / self.value.abs()
+    }
+}
+
+/// Computes the square root of a measured value and propagates its uncertainty to first order.
+///
+/// Since `d√x/dx = 1 / (2√x)`, the root's uncertainty is `σ / (2√x)`, which halves the relative
+/// uncertainty. The linearization breaks down when `σ` is not small compared to `value`; at zero
+/// the propagated uncertainty is infinite unless `σ` is zero.
+///
+/// # Arguments
+/// - `value`: The measured value (must be non-negative).
+/// - `sigma`: Its standard uncertainty; the sign is ignored.
+///
+/// # Returns
+/// - `Ok(Measurement)` with the root and its uncertainty.
+/// - `Err(anyhow::Error)` if `value` is negative or the iteration fails to converge.
+///
+/// ```
+/// let root = sqrtx::square_root_with_uncertainty(16.0, 0.8)?;
+/// assert!((root.value - 4.0).abs() < 1e-10);
+/// assert!((root.uncertainty - 0.1).abs() < 1e-10);
+/// # Ok::<(), sqrtx::Error>(())
+/// ```
+pub fn square_root_with_uncertainty(value: f64, sigma: f64) -> Result<Measurement> {
+    let root = kernel::sqrt(value)?;
+    let sigma = sigma.abs();
+    let uncertainty = if sigma == 0.0 {
+        0.0
+    } else {
+        sigma / (2.0 * root)
+    };
+    Ok(Measurement::new(root, uncertainty))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_square_root_with_uncertainty() -> Result<()> {
+        let input = Measurement::new(2.0, -0.02);
+        let root = square_root_with_uncertainty(input.value, input.uncertainty)?;
+        assert!((root.value - 2f64.sqrt()).abs() < 1e-10);
+        assert!((root.relative_uncertainty() - input.relative_uncertainty() / 2.0).abs() < 1e-12);
+
+        assert_eq!(square_root_with_uncertainty(9.0, 0.0)?.uncertainty, 0.0);
+        assert_eq!(
+            square_root_with_uncertainty(0.0, 0.0)?,
+            Measurement::new(0.0, 0.0)
+        );
+        assert_eq!(
+            square_root_with_uncertainty(0.0, 1e-3)?.uncertainty,
+            f64::INFINITY
+        );
+        assert!(square_root_with_uncertainty(-1.0, 0.1).is_err());
+        Ok(())
+    }
+}
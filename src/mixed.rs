@@ -0,0 +1,89 @@
+//! Mixed-precision square roots: `f32` inputs computed in `f64`.
+//!
+//! Every `f32` is exactly representable as a normal `f64`, so inputs are widened without loss and
+//! scaled by an exact power of four into `[1, 4)` before calling the kernel. That keeps the
+//! kernel's absolute tolerance meaningful for tiny inputs, which would otherwise stop the
+//! iteration long before the result is accurate.
+
+use crate::{kernel, SqrtError};
+
+/// Returns `2^exponent` for exponents within the normal `f64` range.
+fn pow2(exponent: i32) -> f64 {
+    f64::from_bits(((exponent + 1023) as u64) << 52)
+}
+
+/// Computes `√number` in `f64` for an `f32` input.
+///
+/// Zero, negative and non-finite inputs are passed to the kernel unchanged so they produce the
+/// same results and errors as the `f64` APIs.
+pub(crate) fn widened_sqrt(number: f32) -> Result<f64, SqrtError> {
+    let number = f64::from(number);
+    if !(number > 0.0 && number.is_finite()) {
+        return kernel::sqrt(number);
+    }
+
+    let half_exponent = (((number.to_bits() >> 52) as i32) - 1023).div_euclid(2);
+    let scaled = number * pow2(-2 * half_exponent);
+    Ok(kernel::sqrt(scaled)? * pow2(half_exponent))
+}
+
+/// Computes the correctly rounded (round-to-nearest) `f32` square root of `number`.
+///
+/// The `f64` root is rounded to `f32` and then checked exactly: the midpoints between adjacent
+/// `f32` values need at most 25 significant bits, so their squares are exact in `f64` and can be
+/// compared against the input directly. A root can never land on a midpoint, so there are no ties.
+pub(crate) fn rounded_sqrt_f32(number: f32) -> Result<f32, SqrtError> {
+    let root = widened_sqrt(number)?;
+    let mut candidate = root as f32;
+    if candidate == 0.0 {
+        return Ok(candidate);
+    }
+
+    let target = f64::from(number);
+    let midpoint = |a: f32, b: f32| (f64::from(a) + f64::from(b)) / 2.0;
+    loop {
+        let below = f32::from_bits(candidate.to_bits() - 1);
+        let above = f32::from_bits(candidate.to_bits() + 1);
+        let low = midpoint(below, candidate);
+        let high = midpoint(candidate, above);
+        if target < low * low {
+            candidate = below;
+        } else if target > high * high {
+            candidate = above;
+        } else {
+            return Ok(candidate);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_widened_sqrt() -> Result<(), SqrtError> {
+        for number in [1e-38_f32, f32::from_bits(1), 2.0, 0.1, 12345.678, f32::MAX] {
+            let expected = f64::from(number).sqrt();
+            let root = widened_sqrt(number)?;
+            assert!((root - expected).abs() <= expected * 1e-15, "√{number}");
+        }
+        assert_eq!(widened_sqrt(0.0)?, 0.0);
+        assert!(matches!(
+            widened_sqrt(-1.0),
+            Err(SqrtError::NegativeNumber(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_rounded_sqrt_f32_matches_hardware() -> Result<(), SqrtError> {
+        let mut bits = 1_u32;
+        while bits < f32::INFINITY.to_bits() {
+            let number = f32::from_bits(bits);
+            assert_eq!(rounded_sqrt_f32(number)?, number.sqrt(), "√{number}");
+            bits += 0x1357;
+        }
+        assert_eq!(rounded_sqrt_f32(f32::MAX)?, f32::MAX.sqrt());
+        Ok(())
+    }
+}
@@ -0,0 +1,308 @@
+use crate::Result;
+use rayon::prelude::*;
+
+use crate::reduce::{reduce, Reduction};
+use crate::{kernel, SqrtError};
+
+/// Number of elements per partition when reducing a single slice in parallel.
+const NORM_CHUNK_LEN: usize = 8192;
+
+/// Running sum of squares kept as `scale² · sum`, where `scale` is the largest magnitude seen.
+///
+/// Dividing by the running maximum keeps every term in `[0, 1]`, so squaring never overflows or
+/// underflows even for entries near `f64::MAX` or `f64::MIN_POSITIVE` (the LAPACK `dnrm2`
+/// scheme). Partial sums from different threads merge exactly the same way.
+///
+/// `compensation` carries the rounding error of compensated (Neumaier) accumulation and stays
+/// zero for plain accumulation.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct ScaledSumSq {
+    pub(crate) scale: f64,
+    pub(crate) sum: f64,
+    compensation: f64,
+}
+
+impl ScaledSumSq {
+    pub(crate) fn push(mut self, value: f64) -> Self {
+        let magnitude = value.abs();
+        if magnitude > self.scale {
+            let ratio = self.scale / magnitude;
+            self.sum = 1.0 + self.sum * ratio * ratio;
+            self.scale = magnitude;
+        } else if magnitude > 0.0 || magnitude.is_nan() {
+            let ratio = magnitude / self.scale;
+            self.sum += ratio * ratio;
+        }
+        self
+    }
+
+    /// Like [`ScaledSumSq::push`], but tracks the rounding error of every addition.
+    pub(crate) fn push_compensated(mut self, value: f64) -> Self {
+        let magnitude = value.abs();
+        let term = if magnitude > self.scale {
+            let ratio = self.scale / magnitude;
+            self.sum *= ratio * ratio;
+            self.compensation *= ratio * ratio;
+            self.scale = magnitude;
+            1.0
+        } else if magnitude > 0.0 || magnitude.is_nan() {
+            let ratio = magnitude / self.scale;
+            ratio * ratio
+        } else {
+            return self;
+        };
+
+        let sum = self.sum + term;
+        self.compensation += if self.sum.abs() >= term.abs() {
+            (self.sum - sum) + term
+        } else {
+            (term - sum) + self.sum
+        };
+        self.sum = sum;
+        self
+    }
+
+    pub(crate) fn merge(self, other: Self) -> Self {
+        let (large, small) = if self.scale >= other.scale {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        if large.scale == 0.0 {
+            return large;
+        }
+        let ratio = small.scale / large.scale;
+        Self {
+            scale: large.scale,
+            sum: large.sum + small.sum * ratio * ratio,
+            compensation: large.compensation + small.compensation * ratio * ratio,
+        }
+    }
+
+    /// Returns `√(Σ xᵢ²)` as `scale · √sum`.
+    pub(crate) fn norm(self) -> Result<f64> {
+        if self.scale == 0.0 {
+            return Ok(0.0);
+        }
+        Ok(self.scale * kernel::sqrt(self.sum + self.compensation)?)
+    }
+
+    /// Returns `√(Σ xᵢ² / n)` as `scale · √(sum / n)`.
+    pub(crate) fn rms(self, n: usize) -> Result<f64> {
+        if self.scale == 0.0 {
+            return Ok(0.0);
+        }
+        Ok(self.scale * kernel::sqrt((self.sum + self.compensation) / n as f64)?)
+    }
+}
+
+fn sum_sq(values: &[f64], reduction: Reduction) -> ScaledSumSq {
+    let push = match reduction {
+        Reduction::Fast => ScaledSumSq::push,
+        Reduction::Compensated | Reduction::Deterministic => ScaledSumSq::push_compensated,
+    };
+    values
+        .iter()
+        .fold(ScaledSumSq::default(), |acc, &value| push(acc, value))
+}
+
+/// Computes the Frobenius norm `√(Σᵢⱼ aᵢⱼ²)` of a matrix given as a slice of rows.
+///
+/// Rows are reduced in parallel with overflow-safe scaling, so matrices with entries near
+/// `f64::MAX` do not overflow to infinity. Rows may have different lengths.
+///
+/// # Arguments
+/// - `rows`: The rows of the matrix.
+///
+/// # Returns
+/// - `Ok(f64)` if the computation is successful.
+/// - `Err(anyhow::Error)` if the sum of squares fails to converge (e.g. for `NaN` entries).
+pub fn frobenius_norm_rows<R: AsRef<[f64]> + Sync>(rows: &[R]) -> Result<f64> {
+    frobenius_norm_rows_with(rows, Reduction::default())
+}
+
+/// Computes the Frobenius norm of a matrix given as a slice of rows, combining the per-row
+/// partial sums as selected by `reduction`.
+///
+/// # Returns
+/// - `Ok(f64)` if the computation is successful.
+/// - `Err(anyhow::Error)` if the sum of squares fails to converge (e.g. for `NaN` entries).
+pub fn frobenius_norm_rows_with<R: AsRef<[f64]> + Sync>(
+    rows: &[R],
+    reduction: Reduction,
+) -> Result<f64> {
+    let partials = rows.par_iter().map(|row| sum_sq(row.as_ref(), reduction));
+    reduce(
+        partials,
+        reduction,
+        ScaledSumSq::default,
+        ScaledSumSq::merge,
+    )
+    .norm()
+}
+
+/// Computes the root mean square `√(Σ xᵢ² / n)` of `values`.
+///
+/// The slice is reduced in parallel partitions with the same overflow-safe scaling as the norm
+/// helpers.
+///
+/// # Returns
+/// - `Ok(f64)` if the computation is successful.
+/// - `Err(anyhow::Error)` if `values` is empty or the sum of squares fails to converge.
+pub fn rms(values: &[f64]) -> Result<f64> {
+    rms_with(values, Reduction::default())
+}
+
+/// Computes the root mean square of `values`, accumulating and combining partial sums as
+/// selected by `reduction`. Use [`Reduction::Compensated`] for very long inputs.
+///
+/// # Returns
+/// - `Ok(f64)` if the computation is successful.
+/// - `Err(anyhow::Error)` if `values` is empty or the sum of squares fails to converge.
+pub fn rms_with(values: &[f64], reduction: Reduction) -> Result<f64> {
+    if values.is_empty() {
+        return Err(SqrtError::InsufficientData { needed: 1, got: 0 }.into());
+    }
+    let partials = values
+        .par_chunks(NORM_CHUNK_LEN)
+        .map(|chunk| sum_sq(chunk, reduction));
+    reduce(
+        partials,
+        reduction,
+        ScaledSumSq::default,
+        ScaledSumSq::merge,
+    )
+    .rms(values.len())
+}
+
+/// Computes the Frobenius norm `√(Σᵢⱼ aᵢⱼ²)` of an nalgebra matrix.
+///
+/// Uses the same parallel, overflow-safe reduction as [`frobenius_norm_rows`].
+///
+/// # Arguments
+/// - `matrix`: The input matrix.
+///
+/// # Returns
+/// - `Ok(f64)` if the computation is successful.
+/// - `Err(anyhow::Error)` if the sum of squares fails to converge (e.g. for `NaN` entries).
+#[cfg(feature = "nalgebra")]
+pub fn frobenius_norm(matrix: &nalgebra::DMatrix<f64>) -> Result<f64> {
+    frobenius_norm_with(matrix, Reduction::default())
+}
+
+/// Computes the Frobenius norm of an nalgebra matrix, combining the per-column partial sums as
+/// selected by `reduction`.
+///
+/// # Returns
+/// - `Ok(f64)` if the computation is successful.
+/// - `Err(anyhow::Error)` if the sum of squares fails to converge (e.g. for `NaN` entries).
+#[cfg(feature = "nalgebra")]
+pub fn frobenius_norm_with(matrix: &nalgebra::DMatrix<f64>, reduction: Reduction) -> Result<f64> {
+    let partials = matrix
+        .as_slice()
+        .par_chunks(matrix.nrows().max(1))
+        .map(|column| sum_sq(column, reduction));
+    reduce(
+        partials,
+        reduction,
+        ScaledSumSq::default,
+        ScaledSumSq::merge,
+    )
+    .norm()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frobenius_norm_rows() -> Result<()> {
+        let rows = vec![vec![1.0, 2.0], vec![-2.0], vec![], vec![4.0, 0.0]];
+        assert!((frobenius_norm_rows(&rows)? - 5.0).abs() < 1e-10);
+        assert_eq!(frobenius_norm_rows::<Vec<f64>>(&[])?, 0.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_frobenius_norm_rows_no_overflow() -> Result<()> {
+        let big = f64::MAX / 2.0;
+        let rows = [[big, big], [big, big]];
+        let norm = frobenius_norm_rows(&rows)?;
+        assert!(norm.is_finite());
+        assert!((norm / big - 2.0).abs() < 1e-10);
+
+        let tiny = f64::MIN_POSITIVE * 3.0;
+        assert!(
+            (frobenius_norm_rows(&[[tiny, 0.0], [0.0, tiny]])? / tiny - 2f64.sqrt()).abs() < 1e-10
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_frobenius_norm_rows_deterministic() -> Result<()> {
+        let rows: Vec<Vec<f64>> = (0..2_000)
+            .map(|i| {
+                (0..50)
+                    .map(|j| ((i * 50 + j) as f64 * 0.618).sin() * 1e3)
+                    .collect()
+            })
+            .collect();
+        let norm_on = |threads| -> Result<f64> {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .expect("failed to build thread pool")
+                .install(|| frobenius_norm_rows_with(&rows, Reduction::Deterministic))
+        };
+
+        let expected = norm_on(1)?;
+        for threads in [2, 3, 8] {
+            assert_eq!(norm_on(threads)?.to_bits(), expected.to_bits());
+        }
+        assert!((frobenius_norm_rows(&rows)? - expected).abs() / expected < 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn test_compensated_summation() -> Result<()> {
+        // Each 1e-18 term is below half an ulp of the running sum, so plain accumulation drops
+        // all of them while the compensated sum keeps their total of 1e-12.
+        let mut row = vec![1e-9; 1_000_000];
+        row[0] = 1.0;
+        let rows = [row];
+
+        assert_eq!(frobenius_norm_rows_with(&rows, Reduction::Fast)?, 1.0);
+        let compensated = frobenius_norm_rows_with(&rows, Reduction::Compensated)?;
+        assert!((compensated - (1.0 + 5e-13)).abs() < 1e-15);
+        Ok(())
+    }
+
+    #[test]
+    fn test_rms() -> Result<()> {
+        let values = vec![3.0; 3 * NORM_CHUNK_LEN + 5];
+        for reduction in [
+            Reduction::Fast,
+            Reduction::Compensated,
+            Reduction::Deterministic,
+        ] {
+            assert!((rms_with(&values, reduction)? - 3.0).abs() < 1e-12);
+        }
+        assert!((rms(&[f64::MAX, f64::MAX])? - f64::MAX).abs() / f64::MAX < 1e-12);
+        assert!(rms(&[]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_frobenius_norm_rows_nan() {
+        assert!(frobenius_norm_rows(&[[1.0, f64::NAN]]).is_err());
+    }
+
+    #[cfg(feature = "nalgebra")]
+    #[test]
+    fn test_frobenius_norm() -> Result<()> {
+        let matrix = nalgebra::DMatrix::from_row_slice(2, 3, &[1.0, 2.0, 2.0, 0.0, 4.0, 0.0]);
+        assert!((frobenius_norm(&matrix)? - 5.0).abs() < 1e-10);
+        assert!((frobenius_norm(&matrix)? - matrix.norm()).abs() < 1e-10);
+        Ok(())
+    }
+}
@@ -0,0 +1,448 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::{SqrtError, TelemetrySink};
+
+/// Where the elements of a batch are computed.
+///
+/// Every batch API dispatches through the selected backend, so switching backends never
+/// requires changing call sites. The single-pass helpers [`sqrt_filter`](crate::sqrt_filter),
+/// [`sqrt_top_k`](crate::sqrt_top_k) and [`sqrt_histogram`](crate::sqrt_histogram) use
+/// [`ExecutionBackend::Rayon`]; their `_with` variants take the backend from [`SqrtOptions`].
+#[derive(Debug, Clone, Default)]
+pub enum ExecutionBackend {
+    /// Compute every element in order on the calling thread (or async task).
+    Sequential,
+    /// Split the batch into chunks processed on rayon's global thread pool.
+    Rayon,
+    /// Split the batch into chunks processed on a caller-provided rayon pool.
+    CustomPool(Arc<rayon::ThreadPool>),
+    /// Offload the whole batch to tokio's blocking thread pool. This is the default; synchronous
+    /// APIs are already off the async runtime and run it like [`ExecutionBackend::Sequential`].
+    #[default]
+    TokioBlocking,
+}
+
+impl PartialEq for ExecutionBackend {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::CustomPool(a), Self::CustomPool(b)) => Arc::ptr_eq(a, b),
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+}
+
+/// Square root algorithm used for `f64` roots, selected with [`SqrtOptions::algorithm`].
+///
+/// Every algorithm follows the same contract as the default kernel: negative inputs fail with
+/// [`SqrtError::NegativeNumber`], `NaN` and `+∞` with [`SqrtError::NonConvergence`], and the
+/// iterative ones stop at [`SqrtOptions::tolerance`] or [`SqrtOptions::relative_tolerance`]
+/// and fail with
+/// [`SqrtError::DidNotConverge`] at [`SqrtOptions::max_iterations`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    /// Newton–Raphson iteration, quadratically convergent.
+    Newton,
+    /// Halley's method, cubically convergent with an extra division per step.
+    Halley,
+    /// Bisection of a bracket around the root: one bit per step, but guaranteed monotone.
+    Bisection,
+    /// The correctly rounded `FSQRT` (AArch64) / `fsqrt.d` (RISC-V) instruction, or
+    /// [`f64::sqrt`] on other targets (`sqrtsd` on x86_64), which is also correctly rounded; the
+    /// integer-only software path in `soft-float` builds. Ignores the tolerance and iteration cap.
+    Hardware,
+}
+
+/// Load-shedding rules for async batches offloaded to tokio's blocking pool.
+///
+/// If a batch has not started on a blocking thread after `max_queue_delay`, it is taken back:
+/// batches of at most `inline_limit` elements are computed inline on the async task, larger ones
+/// fail with [`SqrtError::Overloaded`](crate::SqrtError::Overloaded).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OverloadPolicy {
+    /// Longest time a batch may wait for a blocking thread.
+    pub max_queue_delay: Duration,
+    /// Largest batch that is computed inline instead of being rejected.
+    pub inline_limit: usize,
+}
+
+/// Tunable behaviour for the `*_with` entry points.
+///
+/// The defaults reproduce the behaviour of the plain functions, so callers only need to set the
+/// options they care about:
+///
+/// ```
+/// use sqrtx::SqrtOptions;
+///
+/// let options = SqrtOptions::new().flush_denormals(true);
+/// assert!(options.flush_denormals);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SqrtOptions {
+    /// Flush subnormal inputs and outputs to (signed) zero, counting them in the batch report.
+    pub flush_denormals: bool,
+    /// Where the batch is computed.
+    pub backend: ExecutionBackend,
+    /// Load shedding for [`ExecutionBackend::TokioBlocking`] in async APIs. `None` waits for a
+    /// blocking thread indefinitely.
+    pub overload: Option<OverloadPolicy>,
+    /// Point in time after which no further chunks are started. Batches that cannot finish fail
+    /// with [`SqrtError::DeadlineExceeded`](crate::SqrtError::DeadlineExceeded), which carries
+    /// the partial results. `None` never gives up.
+    pub deadline: Option<Instant>,
+    /// Receives progress events from batches. `None` reports nothing.
+    pub telemetry: Option<Arc<dyn TelemetrySink>>,
+    /// Saturates every `f64` root into `[low, high]` as it is computed, counting the clamped
+    /// values in the batch report. `NaN` roots are left unchanged. `None` returns roots as they
+    /// are.
+    pub clamp: Option<(f64, f64)>,
+    /// The iteration stops once two successive estimates differ by less than this, for `f64`
+    /// roots. Larger values trade accuracy for speed. `None` uses the kernel default of `1e-10`,
+    /// or no absolute cutoff at all if [`relative_tolerance`](Self::relative_tolerance) is set
+    /// or the algorithm is [`Algorithm::Bisection`], which then splits its bracket as far as it
+    /// goes.
+    pub tolerance: Option<f64>,
+    /// The iteration also stops once the step between estimates is at most this fraction of
+    /// the estimate, so that the accuracy of `f64` roots scales with their magnitude: an
+    /// absolute cutoff is far too coarse for `√1e-300` and needlessly fine for `√1e300`.
+    /// Values just above [`f64::EPSILON`] give nearly full precision.
+    pub relative_tolerance: Option<f64>,
+    /// Iterations after which an `f64` root that has not converged fails with
+    /// [`SqrtError::DidNotConverge`](crate::SqrtError::DidNotConverge). `None` iterates until the
+    /// guesses converge or stall.
+    pub max_iterations: Option<u32>,
+    /// Algorithm for `f64` roots. `None` uses the crate kernel: Newton–Raphson, or the path
+    /// selected by the `soft-float` or `hardware` feature. Ignored when an `only-*` feature fixes
+    /// the algorithm at compile time.
+    pub algorithm: Option<Algorithm>,
+}
+
+impl PartialEq for SqrtOptions {
+    fn eq(&self, other: &Self) -> bool {
+        let same_telemetry = match (&self.telemetry, &other.telemetry) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (a, b) => a.is_none() && b.is_none(),
+        };
+        self.flush_denormals == other.flush_denormals
+            && self.backend == other.backend
+            && self.overload == other.overload
+            && self.deadline == other.deadline
+            && self.clamp == other.clamp
+            && self.tolerance == other.tolerance
+            && self.relative_tolerance == other.relative_tolerance
+            && self.max_iterations == other.max_iterations
+            && self.algorithm == other.algorithm
+            && same_telemetry
+    }
+}
+
+impl SqrtOptions {
+    /// Creates the default set of options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enables or disables flushing of subnormal values to zero.
+    pub fn flush_denormals(mut self, flush: bool) -> Self {
+        self.flush_denormals = flush;
+        self
+    }
+
+    /// Selects the execution backend.
+    pub fn backend(mut self, backend: ExecutionBackend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Sets the load-shedding policy for the blocking pool.
+    pub fn overload(mut self, policy: OverloadPolicy) -> Self {
+        self.overload = Some(policy);
+        self
+    }
+
+    /// Sets the deadline for batches, typically propagated from the caller's request deadline.
+    pub fn deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Sets the sink that receives progress events.
+    pub fn telemetry(mut self, sink: Arc<dyn TelemetrySink>) -> Self {
+        self.telemetry = Some(sink);
+        self
+    }
+
+    /// Clamps roots into `[low, high]`, e.g. the range of a fixed-point consumer. `low` must not
+    /// exceed `high`.
+    pub fn clamp(mut self, low: f64, high: f64) -> Self {
+        self.clamp = Some((low, high));
+        self
+    }
+
+    /// Sets the convergence tolerance. It must be positive; the `soft-float` and `hardware`
+    /// kernels are always correctly rounded and ignore it.
+    pub fn tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = Some(tolerance);
+        self
+    }
+
+    /// Sets a convergence tolerance relative to the magnitude of the root, e.g. `1e-12` for
+    /// about twelve correct digits at any scale.
+    pub fn relative_tolerance(mut self, relative_tolerance: f64) -> Self {
+        self.relative_tolerance = Some(relative_tolerance);
+        self
+    }
+
+    /// Caps the number of iterations per root.
+    pub fn max_iterations(mut self, max_iterations: u32) -> Self {
+        self.max_iterations = Some(max_iterations);
+        self
+    }
+
+    /// Selects the algorithm for `f64` roots, e.g. to benchmark alternatives. An `only-*` feature
+    /// overrides it.
+    pub fn algorithm(mut self, algorithm: Algorithm) -> Self {
+        self.algorithm = Some(algorithm);
+        self
+    }
+}
+
+/// Leading bytes of every encoded options blob.
+const BLOB_MAGIC: [u8; 4] = *b"SQRT";
+/// Format version written by this crate.
+const BLOB_VERSION: u16 = 1;
+
+const TAG_FLUSH_DENORMALS: u16 = 1;
+const TAG_BACKEND: u16 = 2;
+const TAG_OVERLOAD: u16 = 3;
+const TAG_CLAMP: u16 = 4;
+const TAG_TOLERANCE: u16 = 5;
+const TAG_MAX_ITERATIONS: u16 = 6;
+const TAG_ALGORITHM: u16 = 7;
+const TAG_RELATIVE_TOLERANCE: u16 = 8;
+
+impl SqrtOptions {
+    /// Encodes the portable part of the options as a version-tagged blob, for shipping job
+    /// configuration to other processes.
+    ///
+    /// The blob is a header (magic, format version, oldest format version able to read it)
+    /// followed by `(tag, length, value)` records. Process-local settings are not encoded: the
+    /// deadline and telemetry sink are dropped, and [`ExecutionBackend::CustomPool`] is written as
+    /// [`ExecutionBackend::Rayon`].
+    pub fn to_versioned_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&BLOB_MAGIC);
+        bytes.extend_from_slice(&BLOB_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&BLOB_VERSION.to_le_bytes());
+
+        let mut record = |tag: u16, value: &[u8]| {
+            bytes.extend_from_slice(&tag.to_le_bytes());
+            bytes.extend_from_slice(&(value.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(value);
+        };
+        record(TAG_FLUSH_DENORMALS, &[u8::from(self.flush_denormals)]);
+        let backend = match self.backend {
+            ExecutionBackend::Sequential => 0,
+            ExecutionBackend::Rayon | ExecutionBackend::CustomPool(_) => 1,
+            ExecutionBackend::TokioBlocking => 2,
+        };
+        record(TAG_BACKEND, &[backend]);
+        if let Some(policy) = self.overload {
+            let delay = u64::try_from(policy.max_queue_delay.as_nanos()).unwrap_or(u64::MAX);
+            let mut value = delay.to_le_bytes().to_vec();
+            value.extend_from_slice(&(policy.inline_limit as u64).to_le_bytes());
+            record(TAG_OVERLOAD, &value);
+        }
+        if let Some((low, high)) = self.clamp {
+            let mut value = low.to_le_bytes().to_vec();
+            value.extend_from_slice(&high.to_le_bytes());
+            record(TAG_CLAMP, &value);
+        }
+        if let Some(tolerance) = self.tolerance {
+            record(TAG_TOLERANCE, &tolerance.to_le_bytes());
+        }
+        if let Some(max_iterations) = self.max_iterations {
+            record(TAG_MAX_ITERATIONS, &max_iterations.to_le_bytes());
+        }
+        if let Some(relative_tolerance) = self.relative_tolerance {
+            record(TAG_RELATIVE_TOLERANCE, &relative_tolerance.to_le_bytes());
+        }
+        if let Some(algorithm) = self.algorithm {
+            let code = match algorithm {
+                Algorithm::Newton => 0,
+                Algorithm::Halley => 1,
+                Algorithm::Bisection => 2,
+                Algorithm::Hardware => 3,
+            };
+            record(TAG_ALGORITHM, &[code]);
+        }
+        bytes
+    }
+
+    /// Decodes options written by [`SqrtOptions::to_versioned_bytes`] of this or any other crate
+    /// version whose format this version can read.
+    ///
+    /// Records with unknown tags (added by newer versions) and unknown backend codes are ignored,
+    /// leaving the corresponding options at their defaults.
+    ///
+    /// # Returns
+    /// - `Ok(SqrtOptions)` with the decoded options.
+    /// - `Err(SqrtError::InvalidOptionsBlob)` if the blob is truncated, malformed, or requires a
+    ///   newer format version.
+    pub fn from_versioned_bytes(bytes: &[u8]) -> Result<Self, SqrtError> {
+        let invalid = SqrtError::InvalidOptionsBlob;
+        let mut reader = BlobReader(bytes);
+        if reader.take(4).ok_or(invalid("missing header"))? != BLOB_MAGIC {
+            return Err(invalid("bad magic"));
+        }
+        let _version = reader.u16().ok_or(invalid("missing header"))?;
+        let min_reader_version = reader.u16().ok_or(invalid("missing header"))?;
+        if min_reader_version > BLOB_VERSION {
+            return Err(invalid("written for a newer format version"));
+        }
+
+        let mut options = Self::default();
+        while !reader.0.is_empty() {
+            let tag = reader.u16().ok_or(invalid("truncated record"))?;
+            let len = reader.u32().ok_or(invalid("truncated record"))? as usize;
+            let value = reader.take(len).ok_or(invalid("truncated record"))?;
+            match (tag, value) {
+                (TAG_FLUSH_DENORMALS, &[flag]) => options.flush_denormals = flag != 0,
+                (TAG_BACKEND, &[0]) => options.backend = ExecutionBackend::Sequential,
+                (TAG_BACKEND, &[1]) => options.backend = ExecutionBackend::Rayon,
+                (TAG_BACKEND, &[2]) => options.backend = ExecutionBackend::TokioBlocking,
+                (TAG_OVERLOAD, value) if value.len() == 16 => {
+                    let mut value = BlobReader(value);
+                    options.overload =
+                        value
+                            .u64()
+                            .zip(value.u64())
+                            .map(|(delay, limit)| OverloadPolicy {
+                                max_queue_delay: Duration::from_nanos(delay),
+                                inline_limit: usize::try_from(limit).unwrap_or(usize::MAX),
+                            });
+                }
+                (TAG_CLAMP, value) if value.len() == 16 => {
+                    let mut value = BlobReader(value);
+                    options.clamp = value
+                        .u64()
+                        .zip(value.u64())
+                        .map(|(low, high)| (f64::from_bits(low), f64::from_bits(high)));
+                }
+                (TAG_TOLERANCE, value) if value.len() == 8 => {
+                    options.tolerance = BlobReader(value).u64().map(f64::from_bits);
+                }
+                (TAG_MAX_ITERATIONS, value) if value.len() == 4 => {
+                    options.max_iterations = BlobReader(value).u32();
+                }
+                (TAG_RELATIVE_TOLERANCE, value) if value.len() == 8 => {
+                    options.relative_tolerance = BlobReader(value).u64().map(f64::from_bits);
+                }
+                (TAG_ALGORITHM, &[0]) => options.algorithm = Some(Algorithm::Newton),
+                (TAG_ALGORITHM, &[1]) => options.algorithm = Some(Algorithm::Halley),
+                (TAG_ALGORITHM, &[2]) => options.algorithm = Some(Algorithm::Bisection),
+                (TAG_ALGORITHM, &[3]) => options.algorithm = Some(Algorithm::Hardware),
+                (
+                    TAG_FLUSH_DENORMALS
+                    | TAG_OVERLOAD
+                    | TAG_CLAMP
+                    | TAG_TOLERANCE
+                    | TAG_MAX_ITERATIONS
+                    | TAG_RELATIVE_TOLERANCE,
+                    _,
+                ) => return Err(invalid("bad record length")),
+                _ => {}
+            }
+        }
+        Ok(options)
+    }
+}
+
+/// Little-endian cursor over an options blob.
+struct BlobReader<'a>(&'a [u8]);
+
+impl<'a> BlobReader<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.0.len() < len {
+            return None;
+        }
+        let (head, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Some(head)
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        self.take(2).map(|b| u16::from_le_bytes([b[0], b[1]]))
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        self.take(4)
+            .map(|b| u32::from_le_bytes(b.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Option<u64> {
+        self.take(8)
+            .map(|b| u64::from_le_bytes(b.try_into().unwrap()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_versioned_bytes_round_trip() -> Result<(), SqrtError> {
+        let options = SqrtOptions::new()
+            .flush_denormals(true)
+            .backend(ExecutionBackend::Rayon)
+            .overload(OverloadPolicy {
+                max_queue_delay: Duration::from_millis(25),
+                inline_limit: 64,
+            })
+            .clamp(-1.5, 1e300)
+            .tolerance(1e-6)
+            .relative_tolerance(1e-9)
+            .max_iterations(20)
+            .algorithm(Algorithm::Halley);
+        assert_eq!(
+            SqrtOptions::from_versioned_bytes(&options.to_versioned_bytes())?,
+            options
+        );
+        let defaults = SqrtOptions::new();
+        assert_eq!(
+            SqrtOptions::from_versioned_bytes(&defaults.to_versioned_bytes())?,
+            defaults
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_versioned_bytes_skips_unknown_records() -> Result<(), SqrtError> {
+        let mut bytes = SqrtOptions::new()
+            .flush_denormals(true)
+            .to_versioned_bytes();
+        // A newer writer: version 7, still readable by version 1, with an unknown record.
+        bytes[4..6].copy_from_slice(&7u16.to_le_bytes());
+        bytes.extend_from_slice(&99u16.to_le_bytes());
+        bytes.extend_from_slice(&3u32.to_le_bytes());
+        bytes.extend_from_slice(&[1, 2, 3]);
+
+        let options = SqrtOptions::from_versioned_bytes(&bytes)?;
+        assert!(options.flush_denormals);
+        Ok(())
+    }
+
+    #[test]
+    fn test_versioned_bytes_rejects_invalid() {
+        let bytes = SqrtOptions::new().to_versioned_bytes();
+        let mut newer = bytes.clone();
+        newer[6..8].copy_from_slice(&2u16.to_le_bytes());
+
+        for blob in [&b"JUNK"[..], &bytes[..bytes.len() - 1], &newer] {
+            assert!(matches!(
+                SqrtOptions::from_versioned_bytes(blob),
+                Err(SqrtError::InvalidOptionsBlob(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use std::cmp::Ordering;
+
+use rayon::prelude::*;
+
+use crate::batch;
+use crate::{BatchReport, ExecutionBackend, SqrtError, SqrtOptions};
+
+/// Direction for [`square_roots_sorted`](crate::square_roots_sorted).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    /// Smallest root first.
+    #[default]
+    Ascending,
+    /// Largest root first, e.g. for top-K by magnitude.
+    Descending,
+}
+
+/// Stable-sorts `pairs` on the options' backend: in parallel for the rayon backends, on the
+/// calling thread otherwise.
+fn sort_pairs<F>(pairs: &mut [(f64, f64)], options: &SqrtOptions, compare: F)
+where
+    F: Fn(&(f64, f64), &(f64, f64)) -> Ordering + Sync,
+{
+    match &options.backend {
+        ExecutionBackend::Sequential | ExecutionBackend::TokioBlocking => pairs.sort_by(compare),
+        ExecutionBackend::Rayon => pairs.par_sort_by(compare),
+        ExecutionBackend::CustomPool(pool) => pool.install(|| pairs.par_sort_by(&compare)),
+    }
+}
+
+/// Runs the batch and pairs every input with its root, in input order.
+fn run_pairs(
+    numbers: Vec<f64>,
+    options: &SqrtOptions,
+) -> Result<(Vec<(f64, f64)>, BatchReport), SqrtError> {
+    let (roots, report) = batch::run_batch_cancellable(&numbers, options, None)?;
+    Ok((numbers.into_iter().zip(roots).collect(), report))
+}
+
+/// Computes the roots of `numbers` as `(input, root)` pairs sorted by root.
+pub(crate) fn sorted(
+    numbers: Vec<f64>,
+    options: &SqrtOptions,
+    order: SortOrder,
+) -> Result<(Vec<(f64, f64)>, BatchReport), SqrtError> {
+    let (mut pairs, report) = run_pairs(numbers, options)?;
+    match order {
+        SortOrder::Ascending => sort_pairs(&mut pairs, options, |a, b| a.1.total_cmp(&b.1)),
+        SortOrder::Descending => sort_pairs(&mut pairs, options, |a, b| b.1.total_cmp(&a.1)),
+    }
+    Ok((pairs, report))
+}
+
+/// Computes the roots of `numbers` as `(input, root)` pairs sorted by `key`.
+pub(crate) fn sorted_by_key<K, F>(
+    numbers: Vec<f64>,
+    options: &SqrtOptions,
+    key: F,
+) -> Result<(Vec<(f64, f64)>, BatchReport), SqrtError>
+where
+    K: Ord,
+    F: Fn(f64, f64) -> K + Sync,
+{
+    let (mut pairs, report) = run_pairs(numbers, options)?;
+    sort_pairs(&mut pairs, options, |a, b| {
+        key(a.0, a.1).cmp(&key(b.0, b.1))
+    });
+    Ok((pairs, report))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sorted() -> Result<(), SqrtError> {
+        let numbers = vec![9.0, 1.0, 4.0, 0.0];
+        for backend in [ExecutionBackend::Sequential, ExecutionBackend::Rayon] {
+            let options = SqrtOptions::new().backend(backend);
+            let (ascending, report) = sorted(numbers.clone(), &options, SortOrder::Ascending)?;
+            assert_eq!(ascending, [(0.0, 0.0), (1.0, 1.0), (4.0, 2.0), (9.0, 3.0)]);
+            assert_eq!(report.elements, 4);
+
+            let (descending, _) = sorted(numbers.clone(), &options, SortOrder::Descending)?;
+            assert_eq!(descending[0], (9.0, 3.0));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_sorted_by_key_is_stable() -> Result<(), SqrtError> {
+        let numbers: Vec<f64> = (0..10_000).map(|i| f64::from(i % 7)).collect();
+        let options = SqrtOptions::new().backend(ExecutionBackend::Rayon);
+        let (pairs, _) = sorted_by_key(numbers, &options, |input, _| input as u64 % 2)?;
+
+        let (even, odd) = pairs.split_at(pairs.iter().filter(|pair| pair.0 % 2.0 == 0.0).count());
+        assert!(even.iter().all(|pair| pair.0 % 2.0 == 0.0));
+        assert!(odd.iter().all(|pair| pair.0 % 2.0 == 1.0));
+        // Equal keys keep their input order.
+        let inputs: Vec<f64> = even[..4].iter().map(|pair| pair.0).collect();
+        assert_eq!(inputs, [0.0, 2.0, 4.0, 6.0]);
+        Ok(())
+    }
+}
@@ -0,0 +1,132 @@
+use num_bigint::BigUint;
+
+use crate::{Result, SqrtError};
+
+/// A non-negative decimal `mantissa · 10^exponent`.
+struct Decimal {
+    mantissa: BigUint,
+    exponent: i64,
+}
+
+/// Parses `[+|-]digits[.digits][(e|E)[+|-]digits]`, returning whether the number is negative.
+fn parse_decimal(number: &str) -> Option<(bool, Decimal)> {
+    let (negative, unsigned) = match number.as_bytes().first()? {
+        b'-' => (true, &number[1..]),
+        b'+' => (false, &number[1..]),
+        _ => (false, number),
+    };
+    let (significand, exponent) = match unsigned.find(['e', 'E']) {
+        Some(at) => (&unsigned[..at], unsigned[at + 1..].parse::<i64>().ok()?),
+        None => (unsigned, 0),
+    };
+    let (integer, fraction) = significand.split_once('.').unwrap_or((significand, ""));
+
+    let digits = [integer, fraction].concat();
+    if digits.is_empty() || !digits.bytes().all(|digit| digit.is_ascii_digit()) {
+        return None;
+    }
+    let mantissa = BigUint::parse_bytes(digits.as_bytes(), 10)?;
+    let exponent = exponent.checked_sub(i64::try_from(fraction.len()).ok()?)?;
+    Some((negative, Decimal { mantissa, exponent }))
+}
+
+/// Computes the square root of a decimal number to an arbitrary number of decimal places.
+///
+/// The root is computed exactly on big integers and truncated (not rounded) after `digits`
+/// places, so every digit returned is correct.
+///
+/// # Arguments
+/// - `number`: A non-negative decimal such as `"2"`, `"0.0625"` or `"1.5e-300"`.
+/// - `digits`: The number of digits after the decimal point.
+///
+/// # Returns
+/// - `Ok(String)` with the root, e.g. `"1.4142"` for `("2", 4)`.
+/// - `Err(SqrtError::InvalidDecimal)` if `number` is not a decimal number.
+/// - `Err(SqrtError::NegativeNumber)` if `number` is negative and not zero.
+///
+/// ```
+/// let root = sqrtx::square_root_precise("2", 20)?;
+/// assert_eq!(root, "1.41421356237309504880");
+/// # Ok::<(), sqrtx::Error>(())
+/// ```
+pub fn square_root_precise(number: &str, digits: u32) -> Result<String> {
+    let Some((negative, decimal)) = parse_decimal(number) else {
+        return Err(SqrtError::InvalidDecimal(number.to_owned()).into());
+    };
+    if negative && decimal.mantissa != BigUint::ZERO {
+        let value = number.parse().unwrap_or(f64::NEG_INFINITY);
+        return Err(SqrtError::NegativeNumber(value).into());
+    }
+
+    // ⌊√(m · 10^e) · 10^d⌋ = ⌊√(m · 10^(e + 2d))⌋, and for a negative power the integer part of
+    // the radicand has the same floor root.
+    let shift = decimal.exponent.saturating_add(2 * i64::from(digits));
+    let ten = BigUint::from(10u32);
+    let radicand = match u32::try_from(shift.unsigned_abs()) {
+        Ok(power) if shift >= 0 => decimal.mantissa * ten.pow(power),
+        Ok(power) => decimal.mantissa / ten.pow(power),
+        // Too small to reach the last requested digit.
+        Err(_) if shift < 0 => BigUint::ZERO,
+        Err(_) => return Err(SqrtError::InvalidDecimal(number.to_owned()).into()),
+    };
+    let scaled = radicand.sqrt().to_str_radix(10);
+
+    let digits = digits as usize;
+    let padded = format!("{scaled:0>width$}", width = digits + 1);
+    let (integer, fraction) = padded.split_at(padded.len() - digits);
+    Ok(if digits == 0 {
+        integer.to_owned()
+    } else {
+        format!("{integer}.{fraction}")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_square_root_precise_two() -> Result<()> {
+        let root = square_root_precise("2", 1000)?;
+        assert_eq!(root.len(), 1002);
+        assert!(root.starts_with("1.4142135623730950488016887242096980785696718753769480731766797"));
+
+        // The truncated root r satisfies r² ≤ 2 < (r + 10⁻¹⁰⁰⁰)².
+        let scaled = BigUint::parse_bytes(root.replace('.', "").as_bytes(), 10).unwrap();
+        let two = BigUint::from(2u32) * BigUint::from(10u32).pow(2000);
+        assert!(&scaled * &scaled <= two);
+        assert!((&scaled + 1u32) * (&scaled + 1u32) > two);
+        Ok(())
+    }
+
+    #[test]
+    fn test_square_root_precise_formats() -> Result<()> {
+        assert_eq!(square_root_precise("0.0625", 3)?, "0.250");
+        assert_eq!(square_root_precise("+6.25e2", 1)?, "25.0");
+        assert_eq!(square_root_precise("1E-6", 4)?, "0.0010");
+        assert_eq!(square_root_precise("99", 0)?, "9");
+        assert_eq!(square_root_precise("-0.0", 2)?, "0.00");
+        assert_eq!(square_root_precise(".5", 5)?, "0.70710");
+        assert_eq!(square_root_precise("1.5e-300", 2)?, "0.00");
+        Ok(())
+    }
+
+    #[test]
+    fn test_square_root_precise_errors() {
+        for invalid in ["", "-", "1.2.3", "abc", "1e", "1e+", "0x10", "NaN", "."] {
+            let error = square_root_precise(invalid, 2).unwrap_err();
+            assert!(
+                matches!(
+                    crate::error::as_sqrt_error(&error),
+                    Some(SqrtError::InvalidDecimal(_))
+                ),
+                "{invalid:?}"
+            );
+        }
+        let error = square_root_precise("-2.5", 2).unwrap_err();
+        assert!(matches!(
+            crate::error::as_sqrt_error(&error),
+            Some(SqrtError::NegativeNumber(value)) if *value == -2.5
+        ));
+    }
+}
@@ -0,0 +1,214 @@
+use crate::Result;
+use rayon::prelude::*;
+
+use crate::norm::ScaledSumSq;
+use crate::{kernel, SqrtError};
+
+/// Ensures a row-major buffer of `len` elements splits into whole rows of `cols` columns.
+pub(crate) fn check_shape(len: usize, cols: usize) -> Result<(), SqrtError> {
+    if cols == 0 || !len.is_multiple_of(cols) {
+        return Err(SqrtError::ShapeMismatch { len, cols });
+    }
+    Ok(())
+}
+
+/// Scales every row of a row-major matrix to unit Euclidean length, in place and in parallel.
+///
+/// Row norms use the same overflow-safe accumulation as [`frobenius_norm_rows`]. Rows that are
+/// entirely zero are left unchanged.
+///
+/// # Arguments
+/// - `data`: The matrix in row-major order.
+/// - `cols`: The number of columns.
+///
+/// # Returns
+/// - `Ok(())` if every row was normalized.
+/// - `Err(anyhow::Error)` if `data` does not split into rows of `cols` values, or a row norm
+///   fails to converge (e.g. for `NaN` entries).
+///
+/// [`frobenius_norm_rows`]: crate::frobenius_norm_rows
+pub fn l2_normalize_rows(data: &mut [f64], cols: usize) -> Result<()> {
+    check_shape(data.len(), cols)?;
+    data.par_chunks_mut(cols).try_for_each(|row| {
+        let sum_sq = row
+            .iter()
+            .fold(ScaledSumSq::default(), |acc, &value| acc.push(value));
+        if sum_sq.scale > 0.0 {
+            // Divide by the scale and the root separately: their product may overflow.
+            let root = kernel::sqrt(sum_sq.sum)?;
+            row.iter_mut()
+                .for_each(|value| *value = *value / sum_sq.scale / root);
+        }
+        Ok(())
+    })
+}
+
+/// Rescales every column of a row-major matrix linearly onto `[0, 1]`, in place and in parallel.
+///
+/// Constant columns map to `0`.
+///
+/// # Arguments
+/// - `data`: The matrix in row-major order.
+/// - `cols`: The number of columns.
+///
+/// # Returns
+/// - `Ok(Vec<(f64, f64)>)` with the `(min, max)` of every column, so the same transform can be
+///   applied to other data.
+/// - `Err(anyhow::Error)` if `data` does not split into rows of `cols` values.
+pub fn minmax_scale(data: &mut [f64], cols: usize) -> Result<Vec<(f64, f64)>> {
+    check_shape(data.len(), cols)?;
+
+    let identity = || vec![(f64::INFINITY, f64::NEG_INFINITY); cols];
+    let ranges = data
+        .par_chunks(cols)
+        .fold(identity, |mut ranges, row| {
+            for ((min, max), &value) in ranges.iter_mut().zip(row) {
+                *min = min.min(value);
+                *max = max.max(value);
+            }
+            ranges
+        })
+        .reduce(identity, |mut a, b| {
+            for ((min, max), (other_min, other_max)) in a.iter_mut().zip(b) {
+                *min = min.min(other_min);
+                *max = max.max(other_max);
+            }
+            a
+        });
+
+    data.par_chunks_mut(cols).for_each(|row| {
+        for (value, &(min, max)) in row.iter_mut().zip(&ranges) {
+            let span = max - min;
+            *value = if span > 0.0 {
+                (*value - min) / span
+            } else {
+                0.0
+            };
+        }
+    });
+    Ok(ranges)
+}
+
+/// What [`sanitize`] does with invalid square root inputs.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SanitizePolicy {
+    /// Value written over `NaN` entries, or `None` to leave them in place.
+    pub nan_replacement: Option<f64>,
+    /// Whether negative entries (including `-∞`) are clamped to zero.
+    pub clamp_negatives: bool,
+}
+
+impl SanitizePolicy {
+    /// Creates a policy that leaves every value untouched.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces `NaN` entries with `replacement`.
+    pub fn replace_nan(mut self, replacement: f64) -> Self {
+        self.nan_replacement = Some(replacement);
+        self
+    }
+
+    /// Enables or disables clamping negative entries to zero.
+    pub fn clamp_negatives(mut self, enabled: bool) -> Self {
+        self.clamp_negatives = enabled;
+        self
+    }
+}
+
+/// Number of values [`sanitize`] changed, by reason.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SanitizeReport {
+    /// Number of `NaN` entries replaced.
+    pub nans_replaced: usize,
+    /// Number of negative entries clamped to zero.
+    pub negatives_clamped: usize,
+}
+
+impl SanitizeReport {
+    fn merge(self, other: Self) -> Self {
+        Self {
+            nans_replaced: self.nans_replaced + other.nans_replaced,
+            negatives_clamped: self.negatives_clamped + other.negatives_clamped,
+        }
+    }
+}
+
+/// Cleans `data` for square roots in a single parallel pass, as configured by `policy`.
+///
+/// A `NaN` replaced by a negative value is not clamped afterwards; pick a replacement that is
+/// valid input.
+///
+/// # Arguments
+/// - `data`: The values to clean in place.
+/// - `policy`: Which fixes to apply.
+///
+/// # Returns
+/// A [`SanitizeReport`] counting the values that were changed.
+pub fn sanitize(data: &mut [f64], policy: &SanitizePolicy) -> SanitizeReport {
+    data.par_iter_mut()
+        .fold(SanitizeReport::default, |mut report, value| {
+            if value.is_nan() {
+                if let Some(replacement) = policy.nan_replacement {
+                    *value = replacement;
+                    report.nans_replaced += 1;
+                }
+            } else if policy.clamp_negatives && *value < 0.0 {
+                *value = 0.0;
+                report.negatives_clamped += 1;
+            }
+            report
+        })
+        .reduce(SanitizeReport::default, SanitizeReport::merge)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_l2_normalize_rows() -> Result<()> {
+        let mut data = [3.0, 4.0, 0.0, 0.0, f64::MAX, f64::MAX];
+        l2_normalize_rows(&mut data, 2)?;
+        assert!((data[0] - 0.6).abs() < 1e-10 && (data[1] - 0.8).abs() < 1e-10);
+        assert_eq!(&data[2..4], &[0.0, 0.0]);
+        assert!((data[4] - 0.5f64.sqrt()).abs() < 1e-10);
+        Ok(())
+    }
+
+    #[test]
+    fn test_minmax_scale() -> Result<()> {
+        let mut data = [1.0, 5.0, 2.0, 3.0, 5.0, 5.0, 3.0, 5.0];
+        let ranges = minmax_scale(&mut data, 4)?;
+        assert_eq!(ranges, vec![(1.0, 5.0), (5.0, 5.0), (2.0, 3.0), (3.0, 5.0)]);
+        assert_eq!(data, [0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 1.0, 1.0]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_sanitize() {
+        let mut data = [4.0, f64::NAN, -1.0, f64::NEG_INFINITY, -0.0, f64::NAN];
+        assert_eq!(
+            sanitize(&mut data, &SanitizePolicy::new()),
+            SanitizeReport::default()
+        );
+
+        let policy = SanitizePolicy::new().replace_nan(0.0).clamp_negatives(true);
+        let report = sanitize(&mut data, &policy);
+        assert_eq!(report.nans_replaced, 2);
+        assert_eq!(report.negatives_clamped, 2);
+        assert_eq!(data, [4.0, 0.0, 0.0, 0.0, -0.0, 0.0]);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "tiny", ignore = "checks the message text")]
+    fn test_shape_mismatch() {
+        let error = minmax_scale(&mut [1.0, 2.0, 3.0], 2).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Buffer of 3 values does not split into rows of 2 columns"
+        );
+        assert!(l2_normalize_rows(&mut [1.0], 0).is_err());
+    }
+}
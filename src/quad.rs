@@ -0,0 +1,202 @@
+//! IEEE-754 binary128 (quadruple precision) square root on `rustc_apfloat`'s [`Quad`].
+//!
+//! There is no stable `f128` in Rust, so the value type is the software float from
+//! `rustc_apfloat`, and the root is computed on its bit pattern with integer arithmetic like
+//! the `f64` software path.
+
+use rustc_apfloat::ieee::{Double, Quad};
+use rustc_apfloat::{Float, FloatConvert};
+
+use crate::{Result, SqrtError};
+
+const SIGN: u128 = 1 << 127;
+const EXPONENT_MASK: u128 = 0x7fff << 112;
+const MANTISSA_MASK: u128 = (1 << 112) - 1;
+const IMPLICIT_BIT: u128 = 1 << 112;
+const BIAS: i64 = 16383;
+
+/// Returns `(⌊√(m · 2^112)⌋, m · 2^112 − ⌊√(m · 2^112)⌋²)` for `m < 2^114`.
+///
+/// The radicand has up to 226 bits, so it is never materialised: the bit-by-bit method consumes
+/// it two bits at a time from the top, and only the root and the remainder (both at most 115
+/// bits) are kept.
+fn isqrt_rem_shifted(mantissa: u128) -> (u128, u128) {
+    let (mut root, mut remainder) = (0u128, 0u128);
+    for pair in (0..113).rev() {
+        let bits = if pair >= 56 {
+            (mantissa >> (2 * pair - 112)) & 3
+        } else {
+            0
+        };
+        remainder = remainder << 2 | bits;
+        let trial = root << 2 | 1;
+        if remainder >= trial {
+            remainder -= trial;
+            root = root << 1 | 1;
+        } else {
+            root <<= 1;
+        }
+    }
+    (root, remainder)
+}
+
+/// Returns the bits of the correctly rounded (round-half-even) square root of the binary128
+/// value with bit pattern `bits`, following IEEE-754: `±0` and `+∞` map to themselves, negative
+/// numbers and NaN map to NaN.
+fn sqrt_bits(bits: u128) -> u128 {
+    let magnitude = bits & !SIGN;
+    if magnitude == 0 {
+        return bits;
+    }
+    if magnitude > EXPONENT_MASK || bits & SIGN != 0 {
+        return Quad::NAN.to_bits();
+    }
+    if magnitude == EXPONENT_MASK {
+        return bits;
+    }
+
+    // Decompose into `mantissa * 2^exponent` with the implicit bit set, normalising subnormals.
+    let mut exponent = (magnitude >> 112) as i64;
+    let mut mantissa = magnitude & MANTISSA_MASK;
+    if exponent == 0 {
+        let shift = i64::from(mantissa.leading_zeros()) - 15;
+        mantissa <<= shift;
+        exponent = 1 - shift;
+    } else {
+        mantissa |= IMPLICIT_BIT;
+    }
+    let mut exponent = exponent - BIAS - 112;
+
+    // Make the exponent even so it can be halved exactly.
+    if exponent & 1 != 0 {
+        mantissa <<= 1;
+        exponent -= 1;
+    }
+
+    // √(m · 2^112) has exactly 113 significant bits for m in [2^112, 2^114).
+    let (root, remainder) = isqrt_rem_shifted(mantissa);
+    // √M ≥ r + ½ exactly when M − r² > r; an exact tie is impossible.
+    let mut root = root + u128::from(remainder > root);
+    let mut result_exponent = (exponent - 112) / 2 + 112;
+    if root == IMPLICIT_BIT << 1 {
+        root >>= 1;
+        result_exponent += 1;
+    }
+
+    ((result_exponent + BIAS) as u128) << 112 | (root & MANTISSA_MASK)
+}
+
+/// Computes the correctly rounded square root of a quadruple-precision number.
+///
+/// binary128 carries 113 significant bits (about 34 decimal digits) and a wider exponent range
+/// than `f64`, for measurements that need more than `f64` without the cost of
+/// [`square_root_precise`](crate::square_root_precise). The result is the `Quad` nearest to the
+/// exact root (ties to even).
+///
+/// # Arguments
+/// - `number`: The input number (must be non-negative and finite).
+///
+/// # Returns
+/// - `Ok(Quad)` within half an ulp of `√number`.
+/// - `Err(anyhow::Error)` if the input number is negative, infinite or `NaN`, like
+///   [`square_root`](crate::square_root). The error reports the input rounded to `f64`.
+///
+/// ```
+/// use rustc_apfloat::ieee::Quad;
+///
+/// let number: Quad = "2".parse().unwrap();
+/// let root = sqrtx::square_root_quad(number)?;
+/// assert_eq!(root.to_string(), "1.41421356237309504880168872420969798");
+/// # Ok::<(), sqrtx::Error>(())
+/// ```
+pub fn square_root_quad(number: Quad) -> Result<Quad> {
+    if number.is_nan() || (number.is_infinite() && !number.is_negative()) {
+        return Err(SqrtError::NonConvergence {
+            iterations: 1,
+            residual: f64::NAN,
+        }
+        .into());
+    }
+    if number.is_negative() && !number.is_zero() {
+        let rounded: Double = number.convert(&mut false).value;
+        return Err(SqrtError::NegativeNumber(f64::from_bits(rounded.to_bits() as u64)).into());
+    }
+    Ok(Quad::from_bits(sqrt_bits(number.to_bits())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quad(number: &str) -> Quad {
+        number.parse().unwrap()
+    }
+
+    #[test]
+    fn test_square_root_quad_known_values() -> Result<()> {
+        assert_eq!(
+            square_root_quad(quad("2"))?.to_bits(),
+            0x3fff_6a09_e667_f3bc_c908_b2fb_1366_ea95
+        );
+        assert_eq!(
+            square_root_quad(quad("1e40"))?.to_bits(),
+            quad("1e20").to_bits()
+        );
+        assert_eq!(
+            square_root_quad(quad("0.0625"))?.to_bits(),
+            quad("0.25").to_bits()
+        );
+        assert_eq!(
+            square_root_quad(Quad::ZERO)?.to_bits(),
+            Quad::ZERO.to_bits()
+        );
+        assert_eq!(
+            square_root_quad(-Quad::ZERO)?.to_bits(),
+            (-Quad::ZERO).to_bits()
+        );
+
+        // The smallest subnormal is 2^-16494 and the largest finite value just under 2^16384.
+        assert_eq!(
+            square_root_quad(Quad::SMALLEST)?.to_bits(),
+            ((BIAS - 8247) as u128) << 112
+        );
+        let largest = square_root_quad(Quad::largest())?;
+        assert_eq!(largest.to_bits() >> 112, (BIAS + 8191) as u128);
+        Ok(())
+    }
+
+    #[test]
+    fn test_square_root_quad_rounds_to_f64_sqrt() -> Result<()> {
+        // Rounding a correctly rounded 113-bit root to 53 bits never double-rounds, so it must
+        // agree with `f64::sqrt` exactly.
+        for bits in crate::fixtures::random_bits(10_000) {
+            let value = f64::from_bits(bits >> 1);
+            if !value.is_finite() {
+                continue;
+            }
+            let number: Quad = Double::from_bits(value.to_bits().into())
+                .convert(&mut false)
+                .value;
+            let root: Double = square_root_quad(number)?.convert(&mut false).value;
+            assert_eq!(root.to_bits() as u64, value.sqrt().to_bits(), "√{value}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_square_root_quad_errors() {
+        let error = square_root_quad(quad("-2")).unwrap_err();
+        assert!(matches!(
+            crate::error::as_sqrt_error(&error),
+            Some(SqrtError::NegativeNumber(value)) if *value == -2.0
+        ));
+        for number in [Quad::NAN, Quad::INFINITY] {
+            let error = square_root_quad(number).unwrap_err();
+            assert!(matches!(
+                crate::error::as_sqrt_error(&error),
+                Some(SqrtError::NonConvergence { .. })
+            ));
+        }
+        assert!(square_root_quad(-Quad::INFINITY).is_err());
+    }
+}
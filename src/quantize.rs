@@ -0,0 +1,95 @@
+use crate::BatchReport;
+
+/// Affine mapping of roots to integers: `q = round(root / scale) + zero_point`.
+///
+/// Values outside the target type's range saturate to its bounds, like the quantizers of common
+/// ML runtimes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quantization {
+    /// Root value of one integer step. Must be positive and finite; quantized batches fail with
+    /// [`SqrtError::InvalidScale`](crate::SqrtError::InvalidScale) otherwise.
+    pub scale: f64,
+    /// Integer that represents a root of zero.
+    pub zero_point: i32,
+}
+
+impl Quantization {
+    /// Creates a quantization with the given step and zero point.
+    pub fn new(scale: f64, zero_point: i32) -> Self {
+        Self { scale, zero_point }
+    }
+
+    /// Maps a quantized value back to the root it approximates.
+    pub fn dequantize<Q: Quantize>(&self, value: Q) -> f64 {
+        (value.into() - f64::from(self.zero_point)) * self.scale
+    }
+}
+
+/// Integer types that batches can quantize roots into.
+///
+/// Implemented for `u8`, `i8`, `u16` and `i16`.
+pub trait Quantize: Copy + Send + Into<f64> {
+    /// Smallest value of the type.
+    const MIN: f64;
+    /// Largest value of the type.
+    const MAX: f64;
+
+    /// Converts an integral value within `[MIN, MAX]`.
+    fn from_integral(value: f64) -> Self;
+}
+
+macro_rules! impl_quantize {
+    ($($t:ty),*) => {$(
+        impl Quantize for $t {
+            const MIN: f64 = <$t>::MIN as f64;
+            const MAX: f64 = <$t>::MAX as f64;
+
+            fn from_integral(value: f64) -> Self {
+                value as $t
+            }
+        }
+    )*};
+}
+
+impl_quantize!(u8, i8, u16, i16);
+
+/// Quantizes `root`, counting it in the report's `clamped` values if it saturates.
+pub(crate) fn quantize<Q: Quantize>(
+    root: f64,
+    quantization: &Quantization,
+    report: &mut BatchReport,
+) -> Q {
+    let value = (root / quantization.scale).round() + f64::from(quantization.zero_point);
+    let saturated = if value < Q::MIN {
+        Q::MIN
+    } else if value > Q::MAX {
+        Q::MAX
+    } else {
+        return Q::from_integral(value);
+    };
+    report.clamped += 1;
+    Q::from_integral(saturated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quantize_rounds_and_saturates() {
+        let mut report = BatchReport::default();
+        let quantization = Quantization::new(0.5, -128);
+        let values: Vec<i8> = [0.0, 1.2, 1.3, 127.5, 1e9]
+            .iter()
+            .map(|&root| quantize(root, &quantization, &mut report))
+            .collect();
+        assert_eq!(values, [-128, -126, -125, 127, 127]);
+        assert_eq!(report.clamped, 1);
+        assert_eq!(quantization.dequantize(values[2]), 1.5);
+
+        let quantization = Quantization::new(0.01, 10);
+        assert_eq!(quantize::<u8>(0.0, &quantization, &mut report), 10);
+        assert_eq!(quantize::<u16>(-1.0, &quantization, &mut report), 0);
+        assert_eq!(report.clamped, 2);
+    }
+}
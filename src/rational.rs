@@ -0,0 +1,122 @@
+use num_bigint::BigUint;
+use num_rational::Ratio;
+
+/// Whether `a` is a closer approximation of `√n` than `b`, given that `√n` lies strictly between
+/// them.
+fn closer(n: u64, a: (u128, u128), b: (u128, u128)) -> bool {
+    let ((p1, q1), (p2, q2)) = (a, b);
+    // √n is below the midpoint (p1·q2 + p2·q1) / (2·q1·q2) exactly when n·(2·q1·q2)² is below
+    // its square. Both sides need about 260 bits.
+    let midpoint = BigUint::from(p1) * q2 + BigUint::from(p2) * q1;
+    let scale = BigUint::from(q1) * q2 * 2u32;
+    let below_midpoint = BigUint::from(n) * &scale * &scale < &midpoint * &midpoint;
+    let a_is_lower = p1 * q2 < p2 * q1;
+    below_midpoint == a_is_lower
+}
+
+/// Computes the best rational approximation of `√n` with a bounded denominator.
+///
+/// The continued fraction of `√n` is expanded exactly on integers. The result is its last
+/// convergent within the bound or, if closer, the best semiconvergent after it. No other
+/// fraction with a denominator up to `max_denominator` is closer to `√n`.
+///
+/// # Arguments
+/// - `n`: The radicand.
+/// - `max_denominator`: The largest denominator allowed; `0` is treated as `1`.
+///
+/// # Returns
+/// - `Ratio<u64>` in lowest terms. Perfect squares give their exact root over `1`.
+///
+/// ```
+/// use num_rational::Ratio;
+///
+/// assert_eq!(sqrtx::square_root_rational(2, 100), Ratio::new(140, 99));
+/// assert_eq!(sqrtx::square_root_rational(49, 100), Ratio::from_integer(7));
+/// ```
+pub fn square_root_rational(n: u64, max_denominator: u64) -> Ratio<u64> {
+    let root = n.isqrt();
+    if root * root == n {
+        return Ratio::from_integer(root);
+    }
+
+    let (n_wide, root_wide) = (u128::from(n), u128::from(root));
+    let (max_p, max_q) = (u128::from(u64::MAX), u128::from(max_denominator.max(1)));
+    // Convergents p/q, starting from 1/0 and a₀/1, and the state (m, d, a) of the expansion of
+    // the complete quotient (m + √n) / d.
+    let (mut previous, mut current) = ((1u128, 0u128), (root_wide, 1u128));
+    let (mut m, mut d, mut a) = (0u128, 1u128, root_wide);
+
+    loop {
+        m = d * a - m;
+        d = (n_wide - m * m) / d;
+        a = (root_wide + m) / d;
+
+        let next = (a * current.0 + previous.0, a * current.1 + previous.1);
+        if next.0 > max_p || next.1 > max_q {
+            let t = ((max_q - previous.1) / current.1).min((max_p - previous.0) / current.0);
+            let semiconvergent = (previous.0 + t * current.0, previous.1 + t * current.1);
+            let best = if t > 0 && closer(n, semiconvergent, current) {
+                semiconvergent
+            } else {
+                current
+            };
+            // Both terms are within `u64` by the checks above, and convergents are coprime.
+            return Ratio::new_raw(best.0 as u64, best.1 as u64);
+        }
+        (previous, current) = (current, next);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Best approximation found by trying every denominator.
+    fn brute_force(n: u64, max_denominator: u64) -> Ratio<u64> {
+        let root = (n as f64).sqrt();
+        (1..=max_denominator)
+            .flat_map(|q| {
+                let p = (root * q as f64) as u64;
+                [Ratio::new(p, q), Ratio::new(p + 1, q)]
+            })
+            .min_by(|a, b| {
+                let error = |r: &Ratio<u64>| (*r.numer() as f64 / *r.denom() as f64 - root).abs();
+                error(a).total_cmp(&error(b))
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn test_square_root_rational_is_best() {
+        for n in 2..60 {
+            for max_denominator in 1..80 {
+                assert_eq!(
+                    square_root_rational(n, max_denominator),
+                    brute_force(n, max_denominator),
+                    "√{n} with denominator ≤ {max_denominator}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_square_root_rational_extremes() {
+        assert_eq!(square_root_rational(0, 10), Ratio::from_integer(0));
+        assert_eq!(square_root_rational(1, 0), Ratio::from_integer(1));
+        assert_eq!(square_root_rational(2, 0), Ratio::from_integer(1));
+        // √(2⁶⁴ − 1) is just below 2³², which is the nearest integer.
+        assert_eq!(
+            square_root_rational(u64::MAX, 1),
+            Ratio::from_integer(1 << 32)
+        );
+
+        // The numerator bound stops the expansion before the denominator bound does.
+        let root = square_root_rational(u64::MAX - 1, u64::MAX);
+        let value = *root.numer() as f64 / *root.denom() as f64;
+        assert!((value - (u64::MAX as f64).sqrt()).abs() < 1e-6);
+
+        let root = square_root_rational(2, u64::MAX);
+        assert!(*root.denom() > 1 << 60);
+        assert!((*root.numer() as f64 / *root.denom() as f64 - 2f64.sqrt()).abs() < 1e-15);
+    }
+}
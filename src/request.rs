@@ -0,0 +1,166 @@
+use crate::SqrtError;
+
+/// Size limits enforced by [`BatchRequest::try_new`].
+///
+/// The defaults are unlimited, so callers only need to set the limits they care about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchLimits {
+    /// Largest number of elements accepted.
+    pub max_elements: usize,
+    /// Largest input size accepted, in bytes, as counted by
+    /// [`BatchReport::bytes`](crate::BatchReport::bytes).
+    pub max_bytes: usize,
+}
+
+impl Default for BatchLimits {
+    fn default() -> Self {
+        Self {
+            max_elements: usize::MAX,
+            max_bytes: usize::MAX,
+        }
+    }
+}
+
+impl BatchLimits {
+    /// Creates unlimited limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the largest number of elements accepted.
+    pub fn max_elements(mut self, max_elements: usize) -> Self {
+        self.max_elements = max_elements;
+        self
+    }
+
+    /// Sets the largest input size accepted, in bytes.
+    pub fn max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Largest number of `f64` elements allowed by both limits.
+    fn element_limit(&self) -> usize {
+        self.max_elements.min(self.max_bytes / size_of::<f64>())
+    }
+}
+
+/// A batch of inputs whose size has been checked against [`BatchLimits`].
+///
+/// ```
+/// use sqrtx::{BatchLimits, BatchRequest};
+///
+/// let limits = BatchLimits::new().max_elements(1_000);
+/// assert!(BatchRequest::try_new(vec![4.0, 9.0], limits).is_ok());
+/// assert!(BatchRequest::try_new(std::iter::repeat(1.0), limits).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchRequest {
+    numbers: Vec<f64>,
+}
+
+impl BatchRequest {
+    /// Collects `items` into a batch, refusing it as soon as it is known to exceed `limits`.
+    ///
+    /// The iterator's size hint is checked before anything is allocated, and the count is
+    /// checked again while collecting, so an oversized or unbounded input never allocates more
+    /// than the limit allows.
+    ///
+    /// # Arguments
+    /// - `items`: The input numbers.
+    /// - `limits`: The largest batch accepted.
+    ///
+    /// # Returns
+    /// - `Ok(BatchRequest)` if the batch is within both limits.
+    /// - `Err(SqrtError::BatchTooLarge)` otherwise, with the number of elements seen so far.
+    pub fn try_new<I>(items: I, limits: BatchLimits) -> Result<Self, SqrtError>
+    where
+        I: IntoIterator<Item = f64>,
+    {
+        let limit = limits.element_limit();
+        let items = items.into_iter();
+        let (lower, _) = items.size_hint();
+        if lower > limit {
+            return Err(SqrtError::BatchTooLarge {
+                elements: lower,
+                limit,
+            });
+        }
+
+        let mut numbers = Vec::with_capacity(lower);
+        for item in items {
+            if numbers.len() == limit {
+                return Err(SqrtError::BatchTooLarge {
+                    elements: limit + 1,
+                    limit,
+                });
+            }
+            numbers.push(item);
+        }
+        Ok(Self { numbers })
+    }
+
+    /// The input numbers.
+    pub fn as_slice(&self) -> &[f64] {
+        &self.numbers
+    }
+
+    /// Number of input numbers.
+    pub fn len(&self) -> usize {
+        self.numbers.len()
+    }
+
+    /// Whether the batch has no inputs.
+    pub fn is_empty(&self) -> bool {
+        self.numbers.is_empty()
+    }
+
+    /// Returns the input numbers, e.g. to pass to
+    /// [`square_roots_parallel_sync_with`](crate::square_roots_parallel_sync_with).
+    pub fn into_vec(self) -> Vec<f64> {
+        self.numbers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_new_within_limits() -> Result<(), SqrtError> {
+        let limits = BatchLimits::new().max_elements(3).max_bytes(24);
+        let request = BatchRequest::try_new(vec![1.0, 4.0, 9.0], limits)?;
+        assert_eq!(request.len(), 3);
+        assert_eq!(request.as_slice(), [1.0, 4.0, 9.0]);
+        assert!(BatchRequest::try_new([], limits)?.is_empty());
+        assert_eq!(
+            BatchRequest::try_new(vec![2.0], BatchLimits::new())?.into_vec(),
+            [2.0]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_new_rejects_oversized() {
+        // Rejected from the size hint, before collecting.
+        let limits = BatchLimits::new().max_elements(10);
+        assert!(matches!(
+            BatchRequest::try_new(vec![0.0; 11], limits),
+            Err(SqrtError::BatchTooLarge {
+                elements: 11,
+                limit: 10
+            })
+        ));
+
+        // The byte limit is the tighter one here, and the filter hides the length.
+        let limits = BatchLimits::new().max_bytes(20);
+        let items = (0..).map(f64::from).filter(|x| *x >= 0.0);
+        assert!(matches!(
+            BatchRequest::try_new(items, limits),
+            Err(SqrtError::BatchTooLarge {
+                elements: 3,
+                limit: 2
+            })
+        ));
+    }
+}
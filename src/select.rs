@@ -0,0 +1,258 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+
+use crate::Result;
+
+use crate::{batch, BatchReport, ExecutionBackend, SqrtOptions};
+
+/// Computes square roots in parallel and keeps only those accepted by `predicate`.
+///
+/// Rejected roots are dropped as they are computed, so memory use is proportional to the number
+/// of matches rather than to the input. Runs on rayon's global thread pool; use
+/// [`sqrt_filter_with`] to choose the backend and kernel options.
+///
+/// # Arguments
+/// - `numbers`: The input numbers (all must be non-negative).
+/// - `predicate`: Decides whether a root is kept.
+///
+/// # Returns
+/// - `Ok(Vec<(usize, f64)>)` with the `(input index, root)` of every match, in input order.
+/// - `Err(anyhow::Error)` if any input number is negative or fails to converge.
+pub fn sqrt_filter<P>(numbers: &[f64], predicate: P) -> Result<Vec<(usize, f64)>>
+where
+    P: Fn(f64) -> bool + Sync,
+{
+    let (matches, _) = sqrt_filter_with(numbers, predicate, &global_pool())?;
+    Ok(matches)
+}
+
+/// Computes square roots on the configured backend, applying the given options, and keeps only
+/// those accepted by `predicate`.
+///
+/// Like [`sqrt_filter`], each chunk's rejected roots are dropped as soon as it completes.
+/// `predicate` sees the roots after flushing and clamping.
+///
+/// # Arguments
+/// - `numbers`: The input numbers (all must be non-negative).
+/// - `predicate`: Decides whether a root is kept.
+/// - `options`: The options controlling the computation.
+///
+/// # Returns
+/// - `Ok((Vec<(usize, f64)>, BatchReport))` with the `(input index, root)` of every match, in
+///   input order, and a summary of the batch.
+/// - `Err(anyhow::Error)` if any input number is negative or fails to converge, or the deadline
+///   passes; the roots are then not kept, so the `partial` roots of
+///   [`SqrtError::DeadlineExceeded`](crate::SqrtError::DeadlineExceeded) are empty.
+pub fn sqrt_filter_with<P>(
+    numbers: &[f64],
+    predicate: P,
+    options: &SqrtOptions,
+) -> Result<(Vec<(usize, f64)>, BatchReport)>
+where
+    P: Fn(f64) -> bool + Sync,
+{
+    let (chunks, report) = batch::run_batch_folded(numbers, options, |start, roots| {
+        (start..)
+            .zip(roots)
+            .filter(|&(_, root)| predicate(root))
+            .collect::<Vec<_>>()
+    })?;
+    Ok((chunks.concat(), report))
+}
+
+/// Options that run a batch on rayon's global pool with the default kernel.
+fn global_pool() -> SqrtOptions {
+    SqrtOptions::new().backend(ExecutionBackend::Rayon)
+}
+
+/// A root ordered by value, with ties broken in favour of the earlier input.
+#[derive(Debug, Clone, Copy)]
+struct Ranked {
+    root: f64,
+    index: usize,
+}
+
+impl Ord for Ranked {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.root
+            .total_cmp(&other.root)
+            .then_with(|| other.index.cmp(&self.index))
+    }
+}
+
+impl PartialOrd for Ranked {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Ranked {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Ranked {}
+
+/// The `k` best entries seen so far, with the worst of them on top.
+type TopK = BinaryHeap<Reverse<Ranked>>;
+
+fn push_bounded(mut heap: TopK, entry: Ranked, k: usize) -> TopK {
+    if heap.len() < k {
+        heap.push(Reverse(entry));
+    } else if heap.peek().is_some_and(|worst| entry > worst.0) {
+        heap.pop();
+        heap.push(Reverse(entry));
+    }
+    heap
+}
+
+/// Computes square roots in parallel and returns the `k` largest.
+///
+/// Each worker keeps only its current best `k` roots, so the full output is never materialized.
+/// Like [`sqrt_filter`], it runs on rayon's global thread pool; use [`sqrt_top_k_with`] to
+/// choose the backend and kernel options.
+///
+/// # Arguments
+/// - `numbers`: The input numbers (all must be non-negative).
+/// - `k`: The number of roots to return.
+///
+/// # Returns
+/// - `Ok(Vec<(usize, f64)>)` with the `(input index, root)` of the `k` largest roots (fewer if
+///   there are fewer inputs), largest first. Equal roots are ordered by input index.
+/// - `Err(anyhow::Error)` if any input number is negative or fails to converge.
+pub fn sqrt_top_k(numbers: &[f64], k: usize) -> Result<Vec<(usize, f64)>> {
+    let (top, _) = sqrt_top_k_with(numbers, k, &global_pool())?;
+    Ok(top)
+}
+
+/// Computes square roots on the configured backend, applying the given options, and returns the
+/// `k` largest.
+///
+/// Like [`sqrt_top_k`], each chunk is cut down to its best `k` roots as soon as it completes.
+///
+/// # Arguments
+/// - `numbers`: The input numbers (all must be non-negative).
+/// - `k`: The number of roots to return.
+/// - `options`: The options controlling the computation.
+///
+/// # Returns
+/// - `Ok((Vec<(usize, f64)>, BatchReport))` with the `(input index, root)` of the `k` largest
+///   roots, largest first and ties ordered by input index, and a summary of the batch.
+/// - `Err(anyhow::Error)` if any input number is negative or fails to converge, or the deadline
+///   passes, with empty `partial` roots as for [`sqrt_filter_with`].
+pub fn sqrt_top_k_with(
+    numbers: &[f64],
+    k: usize,
+    options: &SqrtOptions,
+) -> Result<(Vec<(usize, f64)>, BatchReport)> {
+    if k == 0 {
+        return Ok((Vec::new(), BatchReport::default()));
+    }
+    let (chunks, report) = batch::run_batch_folded(numbers, options, |start, roots| {
+        (start..)
+            .zip(roots)
+            .fold(TopK::new(), |heap, (index, root)| {
+                push_bounded(heap, Ranked { root, index }, k)
+            })
+    })?;
+    let heap = chunks
+        .into_iter()
+        .flatten()
+        .fold(TopK::new(), |heap, entry| push_bounded(heap, entry.0, k));
+
+    let top = heap
+        .into_sorted_vec()
+        .into_iter()
+        .map(|Reverse(entry)| (entry.index, entry.root))
+        .collect();
+    Ok((top, report))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    use super::*;
+    use crate::{kernel, Algorithm, SqrtError};
+
+    #[test]
+    fn test_sqrt_filter() -> Result<()> {
+        let numbers: Vec<f64> = (0..10_000).map(f64::from).collect();
+        let matches = sqrt_filter(&numbers, |root| root >= 99.0)?;
+        assert_eq!(matches.len(), 10_000 - 99 * 99);
+        assert_eq!(matches[0].0, 99 * 99);
+        assert!(matches.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        assert!(sqrt_filter(&[1.0, -1.0], |_| false).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_sqrt_filter_with_options() -> Result<()> {
+        let numbers: Vec<f64> = (0..3 * batch::CHUNK_LEN).map(|i| i as f64).collect();
+        let expected = sqrt_filter(&numbers, |root| root > 50.0)?;
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(2)
+            .build()
+            .expect("failed to build thread pool");
+        for backend in [
+            ExecutionBackend::Sequential,
+            ExecutionBackend::Rayon,
+            ExecutionBackend::CustomPool(Arc::new(pool)),
+        ] {
+            let options = SqrtOptions::new().backend(backend);
+            let (matches, report) = sqrt_filter_with(&numbers, |root| root > 50.0, &options)?;
+            assert_eq!(matches, expected);
+            assert_eq!(report.elements, numbers.len());
+        }
+
+        // Clamping applies before the predicate sees the roots.
+        let options = SqrtOptions::new().clamp(0.0, 10.0);
+        let (matches, report) = sqrt_filter_with(&numbers, |root| root >= 10.0, &options)?;
+        assert_eq!(matches.len(), numbers.len() - 100);
+        assert_eq!(report.clamped, numbers.len() - 101);
+
+        let options = SqrtOptions::new().deadline(Instant::now() - Duration::from_secs(1));
+        let error = sqrt_filter_with(&numbers, |_| true, &options).unwrap_err();
+        assert!(matches!(
+            crate::error::as_sqrt_error(&error),
+            Some(SqrtError::DeadlineExceeded { completed: 0, partial, .. }) if partial.is_empty()
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_sqrt_top_k() -> Result<()> {
+        let numbers: Vec<f64> = (0..10_000).map(|i| f64::from(i % 100)).collect();
+        let top = sqrt_top_k(&numbers, 3)?;
+        let indices: Vec<usize> = top.iter().map(|entry| entry.0).collect();
+        assert_eq!(indices, [99, 199, 299]);
+        assert!(top
+            .iter()
+            .all(|entry| (entry.1 - 99f64.sqrt()).abs() < 1e-10));
+
+        assert_eq!(sqrt_top_k(&[4.0, 1.0], 5)?.len(), 2);
+        assert!(sqrt_top_k(&numbers, 0)?.is_empty());
+        assert!(sqrt_top_k(&[-1.0], 1).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_sqrt_top_k_with_options() -> Result<()> {
+        let numbers: Vec<f64> = (0..3 * batch::CHUNK_LEN)
+            .map(|i| (i % 1000) as f64)
+            .collect();
+        let expected = sqrt_top_k(&numbers, 5)?;
+        for backend in [ExecutionBackend::Sequential, ExecutionBackend::Rayon] {
+            let options = SqrtOptions::new().backend(backend);
+            assert_eq!(sqrt_top_k_with(&numbers, 5, &options)?.0, expected);
+        }
+
+        let options = SqrtOptions::new().algorithm(Algorithm::Bisection);
+        let (top, _) = sqrt_top_k_with(&numbers, 1, &options)?;
+        assert_eq!(top, [(999, kernel::sqrt_with(999.0, &options)?)]);
+        assert!(sqrt_top_k_with(&[4.0, -1.0], 1, &options).is_err());
+        Ok(())
+    }
+}
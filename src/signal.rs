@@ -0,0 +1,284 @@
+#[cfg(feature = "complex")]
+use num_complex::Complex64;
+use rayon::prelude::*;
+#[cfg(all(feature = "complex", feature = "simd"))]
+use wide::f64x4;
+
+#[cfg(feature = "complex")]
+use crate::{batch::CHUNK_LEN, kernel};
+
+/// Converts a level to decibels as `factor · log₁₀(level)`, clamped from below at `floor_db`.
+///
+/// Zero and negative levels map to the floor instead of `-∞`/`NaN`; `NaN` stays `NaN`.
+fn to_db(level: f64, factor: f64, floor_db: f64) -> f64 {
+    if level.is_nan() {
+        return level;
+    }
+    (factor * level.log10()).max(floor_db)
+}
+
+/// Converts amplitudes to decibels relative to full scale (`20 · log₁₀|a|`).
+///
+/// # Arguments
+/// - `amplitudes`: Linear amplitudes; the sign is ignored.
+/// - `floor_db`: The lowest level reported, e.g. `-120.0`. Silence maps to this value.
+///
+/// # Returns
+/// - `Vec<f64>` with the levels in dB, in input order.
+pub fn amplitude_to_db(amplitudes: &[f64], floor_db: f64) -> Vec<f64> {
+    amplitudes
+        .par_iter()
+        .map(|amplitude| to_db(amplitude.abs(), 20.0, floor_db))
+        .collect()
+}
+
+/// Converts powers to decibels (`10 · log₁₀ p`).
+///
+/// # Arguments
+/// - `powers`: Linear powers (e.g. squared magnitudes).
+/// - `floor_db`: The lowest level reported, e.g. `-120.0`. Zero and negative powers map to this
+///   value.
+///
+/// # Returns
+/// - `Vec<f64>` with the levels in dB, in input order.
+pub fn power_to_db(powers: &[f64], floor_db: f64) -> Vec<f64> {
+    powers
+        .par_iter()
+        .map(|&power| to_db(power, 10.0, floor_db))
+        .collect()
+}
+
+/// Overflow-safe `√(re² + im²)`.
+///
+/// The larger component is factored out, so only a ratio in `[0, 1]` is squared. Infinite
+/// components give `∞` and `NaN` components give `NaN`, like [`f64::hypot`].
+#[cfg(feature = "complex")]
+fn hypot(re: f64, im: f64) -> f64 {
+    let (re, im) = (re.abs(), im.abs());
+    if re.is_infinite() || im.is_infinite() {
+        return f64::INFINITY;
+    }
+
+    let (large, small) = if re >= im { (re, im) } else { (im, re) };
+    if large == 0.0 || large.is_nan() || small.is_nan() {
+        return large + small;
+    }
+
+    let ratio = small / large;
+    // `1 + ratio²` lies in `[1, 2]`, where the kernel cannot fail.
+    large * kernel::sqrt(1.0 + ratio * ratio).unwrap_or(f64::NAN)
+}
+
+/// Computes the principal square root of a real number as a complex number.
+///
+/// Unlike [`square_root`](crate::square_root), negative inputs are not an error: `√-x` is
+/// `i·√x`. `NaN` gives `NaN + 0i`, `+∞` gives `∞ + 0i` and `-∞` gives `0 + ∞i`.
+///
+/// # Arguments
+/// - `number`: The radicand, of any sign.
+///
+/// # Returns
+/// - `Complex64` with a non-negative real part, or a non-negative imaginary part for negative
+///   inputs.
+///
+/// ```
+/// let root = sqrtx::square_root_complex(-4.0);
+/// assert!(root.re == 0.0 && (root.im - 2.0).abs() < 1e-10);
+/// ```
+#[cfg(feature = "complex")]
+pub fn square_root_complex(number: f64) -> Complex64 {
+    // The kernel only rejects `NaN` and `∞` here, whose roots are themselves.
+    let magnitude = kernel::sqrt(number.abs()).unwrap_or(number.abs());
+    if number < 0.0 {
+        Complex64::new(0.0, magnitude)
+    } else {
+        Complex64::new(magnitude, 0.0)
+    }
+}
+
+/// Writes the magnitudes of `chunk` to `magnitudes`, which must be as long, four lanes at a time.
+///
+/// Lanes follow the scaling of [`hypot`], but take the root with the `f64x4` square root. Zero,
+/// infinite and `NaN` inputs, whose lanes would divide `0/0` or `∞/∞`, and which `f64x4::max`
+/// does not propagate, go through [`hypot`] itself.
+#[cfg(all(feature = "complex", feature = "simd"))]
+fn magnitudes_chunk(chunk: &[Complex64], magnitudes: &mut [f64]) {
+    const LANES: usize = 4;
+    for (chunk, magnitudes) in chunk.chunks(LANES).zip(magnitudes.chunks_mut(LANES)) {
+        let (mut re, mut im) = ([0.0; LANES], [0.0; LANES]);
+        for (lane, z) in chunk.iter().enumerate() {
+            re[lane] = z.re;
+            im[lane] = z.im;
+        }
+        let (re, im) = (f64x4::new(re).abs(), f64x4::new(im).abs());
+        let (large, small) = (re.max(im), re.min(im));
+        let ratio = small / large;
+        let lanes = (large * (f64x4::ONE + ratio * ratio).sqrt()).to_array();
+        for ((magnitude, lane), z) in magnitudes.iter_mut().zip(lanes).zip(chunk) {
+            *magnitude = if lane.is_nan() || !(z.re.is_finite() && z.im.is_finite()) {
+                hypot(z.re, z.im)
+            } else {
+                lane
+            };
+        }
+    }
+}
+
+/// Writes the magnitudes of `chunk` to `magnitudes`, which must be as long.
+#[cfg(all(feature = "complex", not(feature = "simd")))]
+fn magnitudes_chunk(chunk: &[Complex64], magnitudes: &mut [f64]) {
+    for (magnitude, z) in magnitudes.iter_mut().zip(chunk) {
+        *magnitude = hypot(z.re, z.im);
+    }
+}
+
+/// Computes the magnitude `|z|` of every complex number, e.g. the bins of an FFT.
+///
+/// Large inputs are processed in parallel, and four values at a time in `wide::f64x4` registers
+/// with the `simd` feature. The computation never overflows for finite inputs, even when
+/// `re² + im²` would exceed `f64::MAX`.
+///
+/// # Arguments
+/// - `values`: The complex numbers.
+///
+/// # Returns
+/// - `Vec<f64>` with the magnitudes, in input order.
+#[cfg(feature = "complex")]
+pub fn complex_magnitudes(values: &[Complex64]) -> Vec<f64> {
+    let mut magnitudes = vec![0.0; values.len()];
+    values
+        .par_chunks(CHUNK_LEN)
+        .zip(magnitudes.par_chunks_mut(CHUNK_LEN))
+        .for_each(|(chunk, magnitudes)| magnitudes_chunk(chunk, magnitudes));
+    magnitudes
+}
+
+/// Computes the level of every complex number in dB (`20 · log₁₀|z|`) in a single pass,
+/// fusing [`complex_magnitudes`] and [`amplitude_to_db`].
+///
+/// # Arguments
+/// - `values`: The complex numbers, e.g. FFT bins.
+/// - `floor_db`: The lowest level reported. Zero-magnitude bins map to this value.
+///
+/// # Returns
+/// - `Vec<f64>` with the levels in dB, in input order.
+#[cfg(feature = "complex")]
+pub fn complex_magnitudes_db(values: &[Complex64], floor_db: f64) -> Vec<f64> {
+    values
+        .par_iter()
+        .map(|z| to_db(hypot(z.re, z.im), 20.0, floor_db))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_amplitude_to_db() {
+        let levels = amplitude_to_db(&[1.0, -0.1, 0.0, f64::NAN], -120.0);
+        assert_eq!(levels[0], 0.0);
+        assert!((levels[1] + 20.0).abs() < 1e-10);
+        assert_eq!(levels[2], -120.0);
+        assert!(levels[3].is_nan());
+    }
+
+    #[test]
+    fn test_power_to_db() {
+        let levels = power_to_db(&[100.0, 0.5, -1.0], -60.0);
+        assert!((levels[0] - 20.0).abs() < 1e-10);
+        assert!((levels[1] + 3.0103).abs() < 1e-4);
+        assert_eq!(levels[2], -60.0);
+    }
+
+    #[cfg(feature = "complex")]
+    #[test]
+    fn test_complex_magnitudes() {
+        let values = [
+            Complex64::new(3.0, -4.0),
+            Complex64::new(0.0, 0.0),
+            Complex64::new(-2.0, 0.0),
+            Complex64::new(f64::MAX / 2.0, f64::MAX / 2.0),
+            Complex64::new(f64::NEG_INFINITY, f64::NAN),
+        ];
+        let magnitudes = complex_magnitudes(&values);
+        assert!((magnitudes[0] - 5.0).abs() < 1e-10);
+        assert_eq!(magnitudes[1], 0.0);
+        assert!((magnitudes[2] - 2.0).abs() < 1e-10);
+        assert!((magnitudes[3] / (f64::MAX / 2.0) - 2f64.sqrt()).abs() < 1e-10);
+        assert_eq!(magnitudes[4], f64::INFINITY);
+    }
+
+    #[cfg(feature = "complex")]
+    #[test]
+    fn test_complex_magnitudes_match_hypot() {
+        let bits: Vec<u64> = crate::fixtures::random_bits(2 * 10_001).collect();
+        let values: Vec<Complex64> = bits
+            .chunks(2)
+            // Ratios of `im` to `re` between 2^-64 and 2^64, so neither part is negligible.
+            .map(|pair| {
+                let re = f64::from_bits(pair[0]);
+                let ratio = f64::from_bits((959 + (pair[1] >> 57)) << 52 | pair[1] >> 12);
+                Complex64::new(re, re * ratio)
+            })
+            .filter(|z| z.re.is_finite() && z.im.is_finite())
+            .collect();
+        let magnitudes = complex_magnitudes(&values);
+        for (z, magnitude) in values.iter().zip(magnitudes) {
+            let expected = z.re.hypot(z.im);
+            assert!(
+                (magnitude - expected).abs() <= 2.0 * f64::EPSILON * expected,
+                "|{z}| = {magnitude}, expected {expected}"
+            );
+        }
+
+        // Every special case in a lane of its own and in the remainder.
+        let special = [
+            Complex64::new(0.0, -0.0),
+            Complex64::new(f64::INFINITY, 1.0),
+            Complex64::new(f64::NAN, 1.0),
+            Complex64::new(1.0, f64::NAN),
+            Complex64::new(f64::NAN, f64::INFINITY),
+        ];
+        let magnitudes = complex_magnitudes(&special);
+        assert_eq!(magnitudes[0], 0.0);
+        assert_eq!(magnitudes[1], f64::INFINITY);
+        assert!(magnitudes[2].is_nan() && magnitudes[3].is_nan());
+        assert_eq!(magnitudes[4], f64::INFINITY);
+    }
+
+    #[cfg(feature = "complex")]
+    #[test]
+    fn test_complex_magnitudes_db() {
+        let values = [Complex64::new(0.6, 0.8), Complex64::new(0.0, 0.0)];
+        let levels = complex_magnitudes_db(&values, -100.0);
+        assert!(levels[0].abs() < 1e-10);
+        assert_eq!(levels[1], -100.0);
+    }
+
+    #[cfg(feature = "complex")]
+    #[test]
+    fn test_square_root_complex() {
+        let root = square_root_complex(-9.0);
+        assert_eq!(root.re, 0.0);
+        assert!((root.im - 3.0).abs() < 1e-10);
+        assert!((square_root_complex(2.25) - Complex64::new(1.5, 0.0)).norm() < 1e-10);
+        assert_eq!(square_root_complex(-0.0), Complex64::new(0.0, 0.0));
+        assert_eq!(
+            square_root_complex(f64::NEG_INFINITY),
+            Complex64::new(0.0, f64::INFINITY)
+        );
+        assert_eq!(
+            square_root_complex(f64::INFINITY),
+            Complex64::new(f64::INFINITY, 0.0)
+        );
+        let nan = square_root_complex(f64::NAN);
+        assert!(nan.re.is_nan() && nan.im == 0.0);
+    }
+
+    #[cfg(feature = "complex")]
+    #[test]
+    fn test_complex_magnitudes_nan() {
+        assert!(complex_magnitudes(&[Complex64::new(1.0, f64::NAN)])[0].is_nan());
+    }
+}
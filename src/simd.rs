@@ -0,0 +1,79 @@
+use wide::{f32x8, f64x4};
+
+use crate::{Result, SqrtFloat};
+
+/// Computes the square roots of the four lanes of a `wide::f64x4`.
+///
+/// Each lane goes through the same kernel as [`square_root`](crate::square_root), so the lanes
+/// match the scalar results exactly and callers working in SIMD registers never need to scatter
+/// their values into a slice and gather the roots back.
+///
+/// # Arguments
+/// - `chunk`: The lanes to take the square roots of (each must be non-negative).
+///
+/// # Returns
+/// - `Ok(f64x4)` with the roots, lane for lane.
+/// - `Err(anyhow::Error)` for the first lane, in index order, that is negative or fails to
+///   converge.
+///
+/// ```
+/// use wide::f64x4;
+///
+/// let roots = sqrtx::square_roots_simd(f64x4::new([1.0, 4.0, 9.0, 16.0]))?;
+/// assert_eq!(roots.to_array(), [1.0, 2.0, 3.0, 4.0]);
+/// # Ok::<(), sqrtx::Error>(())
+/// ```
+pub fn square_roots_simd(chunk: f64x4) -> Result<f64x4> {
+    let mut lanes = chunk.to_array();
+    for lane in &mut lanes {
+        *lane = lane.kernel_sqrt()?;
+    }
+    Ok(f64x4::new(lanes))
+}
+
+/// Computes the square roots of the eight lanes of a `wide::f32x8`.
+///
+/// Each lane is rounded like [`square_root`](crate::square_root) rounds an `f32`.
+///
+/// # Arguments
+/// - `chunk`: The lanes to take the square roots of (each must be non-negative).
+///
+/// # Returns
+/// - `Ok(f32x8)` with the roots, lane for lane.
+/// - `Err(anyhow::Error)` for the first lane, in index order, that is negative or fails to
+///   converge.
+pub fn square_roots_simd_f32(chunk: f32x8) -> Result<f32x8> {
+    let mut lanes = chunk.to_array();
+    for lane in &mut lanes {
+        *lane = lane.kernel_sqrt()?;
+    }
+    Ok(f32x8::new(lanes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::square_root;
+
+    #[test]
+    fn test_square_roots_simd_matches_scalar() -> Result<()> {
+        let lanes = [0.0, 2.0, 0.25, 1e10];
+        let roots = square_roots_simd(f64x4::new(lanes))?.to_array();
+        for (lane, root) in lanes.into_iter().zip(roots) {
+            assert_eq!(root, square_root(lane)?);
+        }
+
+        let lanes = [0.0f32, 1.0, 2.0, 3.0, 0.5, 100.0, 1e-3, 65536.0];
+        let roots = square_roots_simd_f32(f32x8::new(lanes))?.to_array();
+        for (lane, root) in lanes.into_iter().zip(roots) {
+            assert_eq!(root, square_root(lane)?);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_square_roots_simd_rejects_negative_lane() {
+        assert!(square_roots_simd(f64x4::new([1.0, -4.0, 9.0, 16.0])).is_err());
+        assert!(square_roots_simd_f32(f32x8::splat(-1.0)).is_err());
+    }
+}
@@ -0,0 +1,154 @@
+use std::convert::Infallible;
+
+/// Destination for results streamed out of a batch computation.
+///
+/// Implementations receive every result as soon as it is computed, keyed either by its position
+/// in the input or by a caller-supplied id, so large jobs never have to hold the full output in
+/// memory.
+pub trait ResultSink {
+    /// The error type reported when the sink cannot store a value.
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// Stores the square root computed for `key`.
+    fn write(&mut self, key: u64, root: f64) -> Result<(), Self::Error>;
+
+    /// Makes everything written so far durable. Called once after the last value.
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Collects results in memory, ignoring keys. Useful in tests and for small jobs.
+impl ResultSink for Vec<f64> {
+    type Error = Infallible;
+
+    fn write(&mut self, _key: u64, root: f64) -> Result<(), Self::Error> {
+        self.push(root);
+        Ok(())
+    }
+}
+
+/// Stores results in a [`sled::Tree`], with big-endian keys so iteration follows key order and
+/// little-endian `f64` values.
+#[cfg(feature = "sled")]
+pub struct SledSink {
+    tree: sled::Tree,
+}
+
+#[cfg(feature = "sled")]
+impl SledSink {
+    /// Creates a sink writing into `tree`.
+    pub fn new(tree: sled::Tree) -> Self {
+        Self { tree }
+    }
+}
+
+#[cfg(feature = "sled")]
+impl ResultSink for SledSink {
+    type Error = sled::Error;
+
+    fn write(&mut self, key: u64, root: f64) -> Result<(), Self::Error> {
+        self.tree.insert(key.to_be_bytes(), &root.to_le_bytes())?;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.tree.flush()?;
+        Ok(())
+    }
+}
+
+/// Stores results in a SQLite table with the columns `key INTEGER PRIMARY KEY` and `root REAL`.
+///
+/// SQLite keys are signed 64-bit integers, so writing a key of `2^63` or more fails with
+/// [`rusqlite::Error::ToSqlConversionFailure`] rather than wrapping to a negative row id.
+///
+/// All writes happen inside a single transaction that is committed by [`ResultSink::flush`].
+#[cfg(feature = "sqlite")]
+pub struct SqliteSink<'conn> {
+    transaction: Option<rusqlite::Transaction<'conn>>,
+    insert: String,
+}
+
+#[cfg(feature = "sqlite")]
+impl<'conn> SqliteSink<'conn> {
+    /// Creates `table` if needed and starts a transaction for the results.
+    ///
+    /// `table` is interpolated into the SQL statements and must be a trusted identifier.
+    pub fn new(connection: &'conn mut rusqlite::Connection, table: &str) -> rusqlite::Result<Self> {
+        connection.execute(
+            &format!(
+                "CREATE TABLE IF NOT EXISTS {table} (key INTEGER PRIMARY KEY, root REAL NOT NULL)"
+            ),
+            [],
+        )?;
+
+        Ok(Self {
+            transaction: Some(connection.transaction()?),
+            insert: format!("INSERT OR REPLACE INTO {table} (key, root) VALUES (?1, ?2)"),
+        })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl ResultSink for SqliteSink<'_> {
+    type Error = rusqlite::Error;
+
+    fn write(&mut self, key: u64, root: f64) -> Result<(), Self::Error> {
+        let transaction = self
+            .transaction
+            .as_ref()
+            .ok_or(rusqlite::Error::InvalidQuery)?;
+        let key = i64::try_from(key)
+            .map_err(|error| rusqlite::Error::ToSqlConversionFailure(Box::new(error)))?;
+        transaction
+            .prepare_cached(&self.insert)?
+            .execute(rusqlite::params![key, root])?;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        match self.transaction.take() {
+            Some(transaction) => transaction.commit(),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(all(test, any(feature = "sled", feature = "sqlite")))]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_sled_sink() -> anyhow::Result<()> {
+        let db = sled::Config::new().temporary(true).open()?;
+        let mut sink = SledSink::new(db.open_tree("roots")?);
+        sink.write(7, 3.0)?;
+        sink.flush()?;
+
+        let stored = db.open_tree("roots")?.get(7u64.to_be_bytes())?.unwrap();
+        assert_eq!(f64::from_le_bytes(stored.as_ref().try_into()?), 3.0);
+        Ok(())
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_sqlite_sink() -> anyhow::Result<()> {
+        let mut connection = rusqlite::Connection::open_in_memory()?;
+        let mut sink = SqliteSink::new(&mut connection, "roots")?;
+        sink.write(1, 2.0)?;
+        sink.write(2, 4.0)?;
+        assert!(matches!(
+            sink.write(1 << 63, 8.0),
+            Err(rusqlite::Error::ToSqlConversionFailure(_))
+        ));
+        sink.flush()?;
+        drop(sink);
+
+        let total: f64 =
+            connection.query_row("SELECT SUM(root) FROM roots", [], |row| row.get(0))?;
+        assert_eq!(total, 6.0);
+        Ok(())
+    }
+}
@@ -0,0 +1,119 @@
+//! Software `f64` square root for targets without a floating-point unit.
+//!
+//! Everything here works on the IEEE-754 bit pattern with integer arithmetic only, so no float
+//! instructions (or compiler-inserted float emulation calls) are involved.
+
+const SIGN: u64 = 1 << 63;
+const EXPONENT_MASK: u64 = 0x7ff << 52;
+const MANTISSA_MASK: u64 = (1 << 52) - 1;
+const IMPLICIT_BIT: u64 = 1 << 52;
+const QUIET_NAN: u64 = 0x7ff8_0000_0000_0000;
+
+/// Bit-by-bit integer square root, returning `(⌊√n⌋, n − ⌊√n⌋²)`.
+fn isqrt_rem(n: u128) -> (u128, u128) {
+    let mut remainder = n;
+    let mut root = 0;
+    let mut bit = 1 << (126 - (n.leading_zeros() & !1).min(126));
+
+    while bit != 0 {
+        if remainder >= root + bit {
+            remainder -= root + bit;
+            root = (root >> 1) + bit;
+        } else {
+            root >>= 1;
+        }
+        bit >>= 2;
+    }
+
+    (root, remainder)
+}
+
+/// Returns the bits of the correctly rounded (round-half-even) square root of the `f64` with
+/// bit pattern `bits`, following IEEE-754: `±0` and `+∞` map to themselves, negative numbers
+/// and NaN map to NaN.
+#[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+pub(crate) fn sqrt_bits(bits: u64) -> u64 {
+    let magnitude = bits & !SIGN;
+    if magnitude == 0 {
+        return bits;
+    }
+    if magnitude > EXPONENT_MASK || bits & SIGN != 0 {
+        return QUIET_NAN;
+    }
+    if magnitude == EXPONENT_MASK {
+        return bits;
+    }
+
+    // Decompose into `mantissa * 2^exponent` with the implicit bit set, normalising subnormals.
+    let mut exponent = (magnitude >> 52) as i64;
+    let mut mantissa = magnitude & MANTISSA_MASK;
+    if exponent == 0 {
+        let shift = mantissa.leading_zeros() as i64 - 11;
+        mantissa <<= shift;
+        exponent = 1 - shift;
+    } else {
+        mantissa |= IMPLICIT_BIT;
+    }
+    let mut exponent = exponent - 1023 - 52;
+
+    // Make the exponent even so it can be halved exactly.
+    if exponent & 1 != 0 {
+        mantissa <<= 1;
+        exponent -= 1;
+    }
+
+    // √(m · 2^52) has exactly 53 significant bits for m in [2^52, 2^54).
+    let (root, remainder) = isqrt_rem(u128::from(mantissa) << 52);
+    // √M ≥ r + ½ exactly when M − r² > r; an exact tie is impossible.
+    let mut root = root as u64 + u64::from(remainder > root);
+    let mut result_exponent = (exponent - 52) / 2 + 52;
+    if root == IMPLICIT_BIT << 1 {
+        root >>= 1;
+        result_exponent += 1;
+    }
+
+    ((result_exponent + 1023) as u64) << 52 | (root & MANTISSA_MASK)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn soft_sqrt(x: f64) -> f64 {
+        f64::from_bits(sqrt_bits(x.to_bits()))
+    }
+
+    #[test]
+    fn test_matches_hardware_sqrt() {
+        let mut state = 0x9e37_79b9_7f4a_7c15_u64;
+        for _ in 0..100_000 {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let x = f64::from_bits(state & !SIGN);
+            if x.is_finite() {
+                assert_eq!(soft_sqrt(x).to_bits(), x.sqrt().to_bits(), "sqrt({x:e})");
+            }
+        }
+    }
+
+    #[test]
+    fn test_special_values() {
+        for x in [
+            0.0,
+            -0.0,
+            1.0,
+            4.0,
+            2.0,
+            f64::MAX,
+            f64::MIN_POSITIVE,
+            5e-324,
+            f64::INFINITY,
+        ] {
+            assert_eq!(soft_sqrt(x).to_bits(), x.sqrt().to_bits(), "sqrt({x:e})");
+        }
+        assert!(soft_sqrt(-1.0).is_nan());
+        assert!(soft_sqrt(f64::NEG_INFINITY).is_nan());
+        assert!(soft_sqrt(f64::NAN).is_nan());
+    }
+}
@@ -0,0 +1,466 @@
+use crate::Result;
+use rayon::prelude::*;
+
+use crate::reduce::{reduce, Reduction};
+use crate::{kernel, SqrtError};
+
+/// Scale making the MAD a consistent estimator of σ for normal data (`1 / Φ⁻¹(¾)`).
+pub const MAD_SCALE: f64 = 1.4826;
+
+/// Interquartile range of the standard normal distribution (`2 · Φ⁻¹(¾)`).
+pub const NORMAL_IQR: f64 = 1.348_979_5;
+
+fn require(data: &[f64], needed: usize) -> Result<(), SqrtError> {
+    if data.len() < needed {
+        return Err(SqrtError::InsufficientData {
+            needed,
+            got: data.len(),
+        });
+    }
+    Ok(())
+}
+
+fn sorted(data: &[f64]) -> Vec<f64> {
+    let mut sorted = data.to_vec();
+    sorted.sort_unstable_by(f64::total_cmp);
+    sorted
+}
+
+/// Linearly interpolated quantile of sorted, non-empty data (Hyndman & Fan type 7).
+fn quantile_sorted(sorted: &[f64], q: f64) -> f64 {
+    let position = q * (sorted.len() - 1) as f64;
+    let lower = position.floor() as usize;
+    let upper = position.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (position - lower as f64)
+}
+
+/// Computes the median of `data`.
+///
+/// # Returns
+/// - `Ok(f64)` with the median.
+/// - `Err(anyhow::Error)` if `data` is empty.
+pub fn median(data: &[f64]) -> Result<f64> {
+    require(data, 1)?;
+    Ok(quantile_sorted(&sorted(data), 0.5))
+}
+
+/// Estimates the standard deviation from the median absolute deviation, `MAD × 1.4826`.
+///
+/// Unlike the classical standard deviation, up to half of the data can be arbitrary outliers
+/// without breaking the estimate, which makes it suitable for monitoring noisy metrics.
+///
+/// # Returns
+/// - `Ok(f64)` with the scaled MAD.
+/// - `Err(anyhow::Error)` if `data` is empty.
+pub fn mad_sigma(data: &[f64]) -> Result<f64> {
+    let center = median(data)?;
+    let deviations: Vec<f64> = data.iter().map(|value| (value - center).abs()).collect();
+    Ok(MAD_SCALE * median(&deviations)?)
+}
+
+/// Estimates the standard deviation from the interquartile range, `IQR / 1.349`.
+///
+/// Tolerates up to a quarter of the data being outliers.
+///
+/// # Returns
+/// - `Ok(f64)` with the scaled IQR.
+/// - `Err(anyhow::Error)` if `data` has fewer than two values.
+pub fn iqr_sigma(data: &[f64]) -> Result<f64> {
+    require(data, 2)?;
+    let sorted = sorted(data);
+    let iqr = quantile_sorted(&sorted, 0.75) - quantile_sorted(&sorted, 0.25);
+    Ok(iqr / NORMAL_IQR)
+}
+
+/// Number of elements each worker folds before merging in [`VarianceAlgorithm::ParallelWelford`].
+const MOMENTS_CHUNK_LEN: usize = 8192;
+
+/// How [`variance`] and [`std_dev_with`] accumulate the sum of squared deviations.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VarianceAlgorithm {
+    /// Computes the mean first, then sums squared deviations from it. Reads the data twice but
+    /// is the most accurate choice for data that fits in memory.
+    #[default]
+    TwoPass,
+    /// Welford's single-pass update. Numerically stable (no catastrophic cancellation like the
+    /// naive `Σx² − n·x̄²`), slightly less accurate than two passes and slower per element
+    /// because of the division in every step, but reads the data only once.
+    Welford,
+    /// Welford's update on chunks in parallel, merged with Chan et al.'s pairwise formula.
+    /// The fastest option for large inputs on multi-core machines; the merge order depends on
+    /// scheduling, so results can differ in the last bits between runs.
+    ParallelWelford,
+    /// [`VarianceAlgorithm::ParallelWelford`] with a [`Reduction::Deterministic`] merge of its
+    /// fixed-size chunks: bit-identical results on every run for a small merge overhead.
+    ParallelWelfordDeterministic,
+}
+
+/// Count, mean and sum of squared deviations of a sample, mergeable across partitions.
+#[derive(Debug, Clone, Copy, Default)]
+struct Moments {
+    count: f64,
+    mean: f64,
+    m2: f64,
+}
+
+impl Moments {
+    fn push(mut self, value: f64) -> Self {
+        self.count += 1.0;
+        let delta = value - self.mean;
+        self.mean += delta / self.count;
+        self.m2 += delta * (value - self.mean);
+        self
+    }
+
+    fn merge(self, other: Self) -> Self {
+        if other.count == 0.0 {
+            return self;
+        }
+        if self.count == 0.0 {
+            return other;
+        }
+        let count = self.count + other.count;
+        let delta = other.mean - self.mean;
+        Self {
+            count,
+            mean: self.mean + delta * other.count / count,
+            m2: self.m2 + other.m2 + delta * delta * self.count * other.count / count,
+        }
+    }
+
+    fn of(data: &[f64]) -> Self {
+        data.iter()
+            .fold(Self::default(), |moments, &value| moments.push(value))
+    }
+}
+
+fn parallel_moments(data: &[f64], reduction: Reduction) -> Moments {
+    let partials = data.par_chunks(MOMENTS_CHUNK_LEN).map(Moments::of);
+    reduce(partials, reduction, Moments::default, Moments::merge)
+}
+
+/// Computes the sample variance (with Bessel's correction) of `data`.
+///
+/// # Arguments
+/// - `data`: The sample.
+/// - `algorithm`: How the squared deviations are accumulated; see [`VarianceAlgorithm`].
+///
+/// # Returns
+/// - `Ok(f64)` with the variance.
+/// - `Err(anyhow::Error)` if `data` has fewer than two values.
+pub fn variance(data: &[f64], algorithm: VarianceAlgorithm) -> Result<f64> {
+    require(data, 2)?;
+    let n = data.len() as f64;
+    let m2 = match algorithm {
+        VarianceAlgorithm::TwoPass => {
+            let mean = data.iter().sum::<f64>() / n;
+            data.iter()
+                .map(|value| (value - mean) * (value - mean))
+                .sum::<f64>()
+        }
+        VarianceAlgorithm::Welford => Moments::of(data).m2,
+        VarianceAlgorithm::ParallelWelford => parallel_moments(data, Reduction::Fast).m2,
+        VarianceAlgorithm::ParallelWelfordDeterministic => {
+            parallel_moments(data, Reduction::Deterministic).m2
+        }
+    };
+    Ok(m2 / (n - 1.0))
+}
+
+/// Computes the sample standard deviation (with Bessel's correction) of `data`.
+///
+/// # Returns
+/// - `Ok(f64)` with the standard deviation.
+/// - `Err(anyhow::Error)` if `data` has fewer than two values or the variance fails to converge.
+pub fn std_dev(data: &[f64]) -> Result<f64> {
+    std_dev_with(data, VarianceAlgorithm::default())
+}
+
+/// Computes the sample standard deviation of `data` with the given variance algorithm.
+///
+/// # Returns
+/// - `Ok(f64)` with the standard deviation.
+/// - `Err(anyhow::Error)` if `data` has fewer than two values or the variance fails to converge.
+pub fn std_dev_with(data: &[f64], algorithm: VarianceAlgorithm) -> Result<f64> {
+    Ok(kernel::relative_sqrt(variance(data, algorithm)?)?)
+}
+
+/// Standardizes `data` in place to zero mean and unit variance (z-scores).
+///
+/// The mean and population standard deviation are gathered in one parallel Welford pass and
+/// the data is then rescaled in a second parallel pass. Constant data (zero variance) is only
+/// centred, so every value becomes `0` instead of `NaN`.
+///
+/// # Returns
+/// - `Ok((f64, f64))` with the mean and standard deviation that were removed, so the same
+///   transform can be applied to other data.
+/// - `Err(anyhow::Error)` if `data` is empty or the variance fails to converge.
+pub fn standardize(data: &mut [f64]) -> Result<(f64, f64)> {
+    require(data, 1)?;
+    let moments = parallel_moments(data, Reduction::Fast);
+    let mean = moments.mean;
+    let sigma = kernel::relative_sqrt(moments.m2 / moments.count)?;
+    let scale = if sigma > 0.0 { sigma.recip() } else { 1.0 };
+
+    data.par_iter_mut()
+        .for_each(|value| *value = (*value - mean) * scale);
+    Ok((mean, sigma))
+}
+
+/// Quantile function of the standard normal distribution (Acklam's rational approximation,
+/// relative error below `1.2e-9`). `p` must lie in `(0, 1)`.
+fn normal_quantile(p: f64) -> Result<f64, SqrtError> {
+    const A: [f64; 6] = [
+        -3.969683028665376e+01,
+        2.209460984245205e+02,
+        -2.759285104469687e+02,
+        1.38357751867269e+02,
+        -3.066479806614716e+01,
+        2.506628277459239e+00,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e+01,
+        1.615858368580409e+02,
+        -1.556989798598866e+02,
+        6.680131188771972e+01,
+        -1.328068155288572e+01,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-03,
+        -3.223964580411365e-01,
+        -2.400758277161838e+00,
+        -2.549732539343734e+00,
+        4.374664141464968e+00,
+        2.938163982698783e+00,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-03,
+        3.224671290700398e-01,
+        2.445134137142996e+00,
+        3.754408661907416e+00,
+    ];
+    const P_LOW: f64 = 0.02425;
+
+    let tail = |p: f64| -> Result<f64, SqrtError> {
+        let q = kernel::sqrt(-2.0 * p.ln())?;
+        Ok(
+            (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+                / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0),
+        )
+    };
+
+    if p < P_LOW {
+        tail(p)
+    } else if p > 1.0 - P_LOW {
+        Ok(-tail(1.0 - p)?)
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        Ok(
+            (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+                / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0),
+        )
+    }
+}
+
+/// Quantile function of the chi-square distribution with `k` degrees of freedom.
+///
+/// One and two degrees of freedom have closed forms. Otherwise this is the Wilson–Hilferty cube
+/// approximation, except far in the lower tail of few degrees of freedom, where the cube turns
+/// negative; there the distribution function behaves like `(x/2)^(k/2) / Γ(k/2 + 1)` instead.
+fn chi_square_quantile(p: f64, k: f64) -> Result<f64, SqrtError> {
+    if k == 1.0 {
+        let z = normal_quantile((1.0 + p) / 2.0)?;
+        return Ok(z * z);
+    }
+    if k == 2.0 {
+        return Ok(-2.0 * (-p).ln_1p());
+    }
+
+    let h = 2.0 / (9.0 * k);
+    let cube = 1.0 - h + normal_quantile(p)? * kernel::sqrt(h)?;
+    if cube > 0.0 {
+        return Ok(k * cube * cube * cube);
+    }
+    // Γ(k/2 + 1) by the recurrence Γ(x + 1) = x · Γ(x), from Γ(1) = 1 or Γ(1/2) = √π.
+    let (mut x, mut gamma) = if k % 2.0 == 0.0 {
+        (1.0, 1.0)
+    } else {
+        (0.5, std::f64::consts::PI.sqrt())
+    };
+    while x <= k / 2.0 {
+        gamma *= x;
+        x += 1.0;
+    }
+    Ok(2.0 * (p * gamma).powf(2.0 / k))
+}
+
+/// Computes a confidence interval for the population standard deviation.
+///
+/// Uses the chi-square interval `s · √((n−1) / χ²)` for normally distributed data. The
+/// chi-square quantiles are exact for two and three samples and Wilson–Hilferty approximations
+/// otherwise (accurate to about 1% from ten samples on).
+///
+/// # Arguments
+/// - `data`: The sample.
+/// - `confidence`: The confidence level, e.g. `0.95`, strictly between 0 and 1.
+///
+/// # Returns
+/// - `Ok((f64, f64))` with the lower and upper bounds.
+/// - `Err(anyhow::Error)` if `data` has fewer than two values or `confidence` is out of range.
+pub fn std_dev_confidence_interval(data: &[f64], confidence: f64) -> Result<(f64, f64)> {
+    if !(confidence > 0.0 && confidence < 1.0) {
+        return Err(SqrtError::InvalidConfidence(confidence).into());
+    }
+
+    let sigma = std_dev(data)?;
+    let k = (data.len() - 1) as f64;
+    let alpha = 1.0 - confidence;
+    let upper_quantile = chi_square_quantile(1.0 - alpha / 2.0, k)?;
+    let lower_quantile = chi_square_quantile(alpha / 2.0, k)?;
+
+    Ok((
+        sigma * kernel::relative_sqrt(k / upper_quantile)?,
+        sigma * kernel::relative_sqrt(k / lower_quantile)?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_median() -> Result<()> {
+        assert_eq!(median(&[3.0, 1.0, 2.0])?, 2.0);
+        assert_eq!(median(&[4.0, 1.0, 2.0, 3.0])?, 2.5);
+        Ok(())
+    }
+
+    #[test]
+    fn test_robust_sigmas_ignore_outliers() -> Result<()> {
+        let mut data: Vec<f64> = (1..=9).map(f64::from).collect();
+        let mad = mad_sigma(&data)?;
+        let iqr = iqr_sigma(&data)?;
+        assert!((mad - 2.0 * MAD_SCALE).abs() < 1e-12);
+        assert!((iqr - 4.0 / NORMAL_IQR).abs() < 1e-12);
+
+        data[8] = 1e12;
+        assert_eq!(mad_sigma(&data)?, mad);
+        assert_eq!(iqr_sigma(&data)?, iqr);
+        Ok(())
+    }
+
+    #[test]
+    fn test_std_dev() -> Result<()> {
+        let data = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        assert!((std_dev(&data)? - (32.0f64 / 7.0).sqrt()).abs() < 1e-10);
+
+        // Accurate relative to the spread, not to one, for data in small units.
+        let sigma = std_dev(&[0.0, 1e-9, 2e-9])?;
+        assert!((sigma - 1e-9).abs() <= 1e-9 * 1e-14, "{sigma}");
+        Ok(())
+    }
+
+    #[test]
+    fn test_variance_algorithms_agree() -> Result<()> {
+        // A large offset makes naive accumulation lose every significant digit.
+        let data: Vec<f64> = (0..3 * MOMENTS_CHUNK_LEN + 11)
+            .map(|i| 1e9 + (i % 7) as f64)
+            .collect();
+        let expected = variance(&data, VarianceAlgorithm::TwoPass)?;
+        assert!((expected - 4.0).abs() < 1e-3);
+
+        for algorithm in [
+            VarianceAlgorithm::Welford,
+            VarianceAlgorithm::ParallelWelford,
+        ] {
+            assert!(
+                (variance(&data, algorithm)? - expected).abs() < 1e-6,
+                "{algorithm:?}"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_standardize() -> Result<()> {
+        let mut data = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        let (mean, sigma) = standardize(&mut data)?;
+        assert_eq!((mean, sigma), (5.0, 2.0));
+        assert_eq!(data[0], -1.5);
+        assert_eq!(data[7], 2.0);
+
+        let mut small = [2e-12, 4e-12, 4e-12, 4e-12, 5e-12, 5e-12, 7e-12, 9e-12];
+        let (_, sigma) = standardize(&mut small)?;
+        assert!((sigma - 2e-12).abs() <= 2e-12 * 1e-14, "{sigma}");
+
+        let mut constant = [3.0; 4];
+        assert_eq!(standardize(&mut constant)?, (3.0, 0.0));
+        assert_eq!(constant, [0.0; 4]);
+        assert!(standardize(&mut []).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_normal_quantile() -> Result<()> {
+        assert!(normal_quantile(0.5)?.abs() < 1e-12);
+        assert!((normal_quantile(0.975)? - 1.959_963_985).abs() < 1e-8);
+        assert!((normal_quantile(0.001)? + 3.090_232_306).abs() < 1e-8);
+        Ok(())
+    }
+
+    #[test]
+    fn test_std_dev_confidence_interval() -> Result<()> {
+        // Ten samples with s = 1, so the bounds are √(9 / χ²₀.₉₇₅) and √(9 / χ²₀.₀₂₅).
+        let data: Vec<f64> = (0..10)
+            .map(|i| f64::from(i) * (12.0f64 / 110.0).sqrt())
+            .collect();
+        assert!((std_dev(&data)? - 1.0).abs() < 1e-10);
+
+        let (lower, upper) = std_dev_confidence_interval(&data, 0.95)?;
+        assert!((lower - (9.0f64 / 19.023).sqrt()).abs() < 1e-2);
+        assert!((upper - (9.0f64 / 2.700).sqrt()).abs() < 1e-2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_std_dev_confidence_interval_few_samples() -> Result<()> {
+        // Two samples with s = 1/√2: one degree of freedom, χ²₀.₉₇₅ = 5.0239, χ²₀.₀₂₅ = 0.000982.
+        let (lower, upper) = std_dev_confidence_interval(&[1.0, 2.0], 0.95)?;
+        assert!((lower - (0.5f64 / 5.0239).sqrt()).abs() < 1e-4, "{lower}");
+        assert!(
+            (upper - (0.5f64 / 0.000_982_07).sqrt()).abs() < 1e-2,
+            "{upper}"
+        );
+        let (lower, upper) = std_dev_confidence_interval(&[1.0, 2.0], 0.99)?;
+        assert!(0.0 < lower && lower < upper && upper.is_finite());
+
+        // Three samples with s = 1: two degrees of freedom, χ²_p = −2 ln(1 − p).
+        let (lower, upper) = std_dev_confidence_interval(&[1.0, 2.0, 3.0], 0.999)?;
+        assert!((lower - (2.0 / (-2.0 * 0.0005f64.ln())).sqrt()).abs() < 1e-10);
+        assert!((upper - (2.0 / (-2.0 * 0.9995f64.ln())).sqrt()).abs() < 1e-6);
+
+        // Four samples far in the lower tail, past where the cube approximation turns negative.
+        let (lower, upper) = std_dev_confidence_interval(&[1.0, 2.0, 3.0, 4.0], 0.999_999)?;
+        assert!(0.0 < lower && lower < upper && upper.is_finite());
+        assert!(chi_square_quantile(1e-6, 3.0)? > 0.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_std_dev_confidence_interval_invalid() {
+        assert!(std_dev_confidence_interval(&[1.0, 2.0], 1.0).is_err());
+        assert!(std_dev_confidence_interval(&[1.0, 2.0], f64::NAN).is_err());
+        assert!(std_dev_confidence_interval(&[1.0], 0.9).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "tiny", ignore = "checks the message text")]
+    fn test_insufficient_data() {
+        assert_eq!(
+            mad_sigma(&[]).unwrap_err().to_string(),
+            "Not enough data: needed at least 1 values, got 0"
+        );
+        assert!(iqr_sigma(&[1.0]).is_err());
+    }
+}
@@ -0,0 +1,83 @@
+//! Stochastic rounding of `f64` results to `f32`.
+//!
+//! Round-to-nearest always rounds a given value the same way, so small biases add up when
+//! rounded values are accumulated repeatedly (e.g. in low-precision training). Stochastic
+//! rounding picks one of the two neighbouring `f32` values with probability proportional to
+//! proximity, which makes the rounding unbiased in expectation.
+
+/// A source of uniformly distributed random bits for stochastic rounding.
+///
+/// The crate does not ship a generator; implement this for the RNG the application already uses,
+/// or pass a closure returning `u32`.
+pub trait RandomSource {
+    /// Returns 32 uniformly distributed random bits.
+    fn next_u32(&mut self) -> u32;
+}
+
+impl<F: FnMut() -> u32> RandomSource for F {
+    fn next_u32(&mut self) -> u32 {
+        self()
+    }
+}
+
+/// Rounds `value` to one of its two neighbouring `f32` values, choosing the upper one with
+/// probability `(value − lower) / (upper − lower)`.
+///
+/// Values that are exactly representable are returned unchanged; values beyond the `f32` range
+/// round to infinity as with `as f32`.
+pub(crate) fn round_f32<R: RandomSource + ?Sized>(value: f64, rng: &mut R) -> f32 {
+    let nearest = value as f32;
+    if f64::from(nearest) == value || !nearest.is_finite() {
+        return nearest;
+    }
+
+    let (lower, upper) = if f64::from(nearest) < value {
+        (nearest, nearest.next_up())
+    } else {
+        (nearest.next_down(), nearest)
+    };
+    let (lower_wide, upper_wide) = (f64::from(lower), f64::from(upper));
+    let probability = (value - lower_wide) / (upper_wide - lower_wide);
+    let draw = f64::from(rng.next_u32()) / 4_294_967_296.0;
+    if draw < probability {
+        upper
+    } else {
+        lower
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_f32_extremes() {
+        let value = 1.0 + f64::from(f32::EPSILON) / 4.0;
+        assert_eq!(round_f32(value, &mut || 0), 1.0_f32.next_up());
+        assert_eq!(round_f32(value, &mut || u32::MAX), 1.0);
+        assert_eq!(round_f32(0.5, &mut || 0), 0.5);
+        assert_eq!(round_f32(f64::MAX, &mut || 0), f32::INFINITY);
+    }
+
+    #[test]
+    fn test_round_f32_unbiased() {
+        // splitmix64, keeping the high half.
+        let mut state = 0x9e37_79b9_7f4a_7c15_u64;
+        let mut rng = || {
+            state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            ((z ^ (z >> 31)) >> 32) as u32
+        };
+
+        let value = 0.1_f64;
+        let draws = 100_000;
+        let mean = (0..draws)
+            .map(|_| f64::from(round_f32(value, &mut rng)))
+            .sum::<f64>()
+            / f64::from(draws);
+        let ulp = f64::from(0.1_f32.next_up() - 0.1_f32);
+        assert!((mean - value).abs() < ulp / 50.0);
+    }
+}
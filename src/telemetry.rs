@@ -0,0 +1,69 @@
+use std::fmt::Debug;
+use std::time::Duration;
+
+use crate::{BatchReport, SqrtError};
+
+/// Receives progress events from batch computations, for forwarding to an observability system.
+///
+/// Every method has an empty default, so implementations only override the events they need.
+/// Methods may be called concurrently from worker threads and should return quickly.
+pub trait TelemetrySink: Debug + Send + Sync {
+    /// A batch of `elements` inputs started.
+    fn job_started(&self, elements: usize) {
+        let _ = elements;
+    }
+
+    /// A chunk of `elements` inputs finished after `busy` time on one worker.
+    fn chunk_completed(&self, elements: usize, busy: Duration) {
+        let _ = (elements, busy);
+    }
+
+    /// A batch finished successfully.
+    fn job_finished(&self, report: &BatchReport) {
+        let _ = report;
+    }
+
+    /// A batch failed with `error`.
+    fn error(&self, error: &SqrtError) {
+        let _ = error;
+    }
+}
+
+/// A [`TelemetrySink`] that ignores every event.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NoopTelemetry;
+
+impl TelemetrySink for NoopTelemetry {}
+
+/// A [`TelemetrySink`] that emits every event through [`tracing`], under the `sqrtx` target.
+///
+/// Job-level events are logged at `INFO` (`ERROR` for failures), chunks at `TRACE`.
+#[cfg(feature = "tracing")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TracingTelemetry;
+
+#[cfg(feature = "tracing")]
+impl TelemetrySink for TracingTelemetry {
+    fn job_started(&self, elements: usize) {
+        tracing::info!(target: "sqrtx", elements, "batch started");
+    }
+
+    fn chunk_completed(&self, elements: usize, busy: Duration) {
+        tracing::trace!(target: "sqrtx", elements, ?busy, "chunk completed");
+    }
+
+    fn job_finished(&self, report: &BatchReport) {
+        tracing::info!(
+            target: "sqrtx",
+            elements = report.elements,
+            flushed = report.flushed,
+            clamped = report.clamped,
+            elapsed = ?report.elapsed,
+            "batch finished"
+        );
+    }
+
+    fn error(&self, error: &SqrtError) {
+        tracing::error!(target: "sqrtx", %error, "batch failed");
+    }
+}
@@ -0,0 +1,196 @@
+use std::ops::Deref;
+
+use crate::Result;
+
+use crate::kernel::{self, newton_nth_root};
+use crate::norm::ScaledSumSq;
+use crate::SqrtError;
+
+/// A `Vec<f64>` wrapper with chainable, in-place root operations.
+///
+/// Every operation consumes the vector and hands it back, transforming the elements in the same
+/// buffer, so several passes over the data never allocate:
+///
+/// ```
+/// use sqrtx::SqrtVec;
+///
+/// let stats = SqrtVec::from(vec![9.0, 16.0]).sqrt()?.normalize()?.stats();
+/// assert!((stats.max - 0.8).abs() < 1e-10);
+/// # Ok::<(), sqrtx::Error>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SqrtVec {
+    data: Vec<f64>,
+}
+
+/// Summary statistics of a [`SqrtVec`], as returned by [`SqrtVec::stats`].
+///
+/// For an empty vector every field except `len` is `NaN`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VecStats {
+    /// Number of elements.
+    pub len: usize,
+    /// Arithmetic mean.
+    pub mean: f64,
+    /// Population standard deviation.
+    pub std_dev: f64,
+    /// Smallest element.
+    pub min: f64,
+    /// Largest element.
+    pub max: f64,
+}
+
+impl SqrtVec {
+    /// Wraps `data` without copying it.
+    pub fn new(data: Vec<f64>) -> Self {
+        Self { data }
+    }
+
+    /// Returns the underlying vector.
+    pub fn into_inner(self) -> Vec<f64> {
+        self.data
+    }
+
+    /// Replaces every element with its square root.
+    ///
+    /// # Returns
+    /// - `Ok(SqrtVec)` if the computation is successful.
+    /// - `Err(anyhow::Error)` if any element is negative or fails to converge.
+    pub fn sqrt(mut self) -> Result<Self> {
+        for value in &mut self.data {
+            *value = kernel::sqrt(*value)?;
+        }
+        Ok(self)
+    }
+
+    /// Replaces every element with its real `degree`-th root, see [`nth_root`](crate::nth_root).
+    ///
+    /// # Returns
+    /// - `Ok(SqrtVec)` if the computation is successful.
+    /// - `Err(anyhow::Error)` if `degree` is zero, or any element fails: a negative one under an
+    ///   even degree, or one that fails to converge.
+    pub fn nth_root(mut self, degree: u32) -> Result<Self> {
+        for value in &mut self.data {
+            *value = newton_nth_root(*value, degree)?;
+        }
+        Ok(self)
+    }
+
+    /// Scales the vector to unit Euclidean length.
+    ///
+    /// # Returns
+    /// - `Ok(SqrtVec)` if the computation is successful.
+    /// - `Err(anyhow::Error)` if the vector has zero norm or its norm fails to converge.
+    pub fn normalize(mut self) -> Result<Self> {
+        // Scaled accumulation, so that squares of elements above ~1e154 do not overflow.
+        let sum_sq = self
+            .data
+            .iter()
+            .fold(ScaledSumSq::default(), |acc, &value| acc.push(value));
+        let root = kernel::sqrt(sum_sq.sum)?;
+        if sum_sq.scale == 0.0 {
+            return Err(SqrtError::ZeroNorm.into());
+        }
+
+        // Divide by the scale and the root separately: their product may overflow.
+        for value in &mut self.data {
+            *value = *value / sum_sq.scale / root;
+        }
+        Ok(self)
+    }
+
+    /// Computes summary statistics of the current elements.
+    pub fn stats(&self) -> VecStats {
+        let len = self.data.len();
+        if len == 0 {
+            return VecStats {
+                len,
+                mean: f64::NAN,
+                std_dev: f64::NAN,
+                min: f64::NAN,
+                max: f64::NAN,
+            };
+        }
+
+        let mean = self.data.iter().sum::<f64>() / len as f64;
+        let variance = self
+            .data
+            .iter()
+            .map(|value| (value - mean) * (value - mean))
+            .sum::<f64>()
+            / len as f64;
+
+        VecStats {
+            len,
+            mean,
+            std_dev: kernel::sqrt(variance).unwrap_or(f64::NAN),
+            min: self.data.iter().copied().fold(f64::INFINITY, f64::min),
+            max: self.data.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        }
+    }
+}
+
+impl Deref for SqrtVec {
+    type Target = [f64];
+
+    fn deref(&self) -> &[f64] {
+        &self.data
+    }
+}
+
+impl From<Vec<f64>> for SqrtVec {
+    fn from(data: Vec<f64>) -> Self {
+        Self::new(data)
+    }
+}
+
+impl From<SqrtVec> for Vec<f64> {
+    fn from(vec: SqrtVec) -> Self {
+        vec.into_inner()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chained_ops_reuse_buffer() -> Result<()> {
+        let data = vec![16.0, 81.0];
+        let ptr = data.as_ptr();
+        let vec = SqrtVec::from(data).sqrt()?.nth_root(2)?;
+        assert_eq!(vec.as_ptr(), ptr);
+        assert!((vec[0] - 2.0).abs() < 1e-10);
+        assert!((vec[1] - 3.0).abs() < 1e-10);
+        Ok(())
+    }
+
+    #[test]
+    fn test_normalize_and_stats() -> Result<()> {
+        let stats = SqrtVec::from(vec![3.0, 4.0]).normalize()?.stats();
+        assert_eq!(stats.len, 2);
+        assert!((stats.mean - 0.7).abs() < 1e-10);
+        assert!((stats.std_dev - 0.1).abs() < 1e-10);
+        assert!((stats.min - 0.6).abs() < 1e-10);
+
+        for scale in [1e300, 1e-300, f64::MIN_POSITIVE / 4.0] {
+            let unit = SqrtVec::from(vec![3.0 * scale, -4.0 * scale]).normalize()?;
+            assert!((unit[0] - 0.6).abs() < 1e-15 && (unit[1] + 0.8).abs() < 1e-15);
+        }
+        let unit = SqrtVec::from(vec![f64::MAX, f64::MAX]).normalize()?;
+        assert!((unit[0] - 0.5f64.sqrt()).abs() < 1e-15);
+        Ok(())
+    }
+
+    #[test]
+    #[cfg_attr(feature = "tiny", ignore = "checks the message text")]
+    fn test_errors() {
+        let error = SqrtVec::from(vec![0.0, 0.0]).normalize().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Cannot normalize a vector with zero norm"
+        );
+        assert!(SqrtVec::from(vec![4.0, -1.0]).sqrt().is_err());
+        assert!(SqrtVec::from(vec![4.0]).nth_root(0).is_err());
+    }
+}